@@ -1,14 +1,29 @@
-use std::{borrow::Borrow, sync::Arc};
+use std::{
+    borrow::Borrow,
+    cell::Cell,
+    future::{poll_fn, Future},
+    pin::pin,
+    rc::Rc,
+    sync::Arc,
+    task::Poll,
+};
 
-use async_channel::{bounded, Receiver};
+use async_channel::bounded;
 use async_lock::RwLock;
-use ed25519_dalek::Signature;
-use web_sys::{js_sys::Object, Document, Window};
+use ed25519_dalek::{Signature, VerifyingKey};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::{Function, Object, Promise},
+    wasm_bindgen::JsValue,
+    Document, Window,
+};
 
 use crate::{
-    events::InitEvents, send_wallet_event, Cluster, SendOptions, SignInOutput, SignedMessageOutput,
-    SigninInput, Wallet, WalletAccount, WalletError, WalletEvent, WalletEventReceiver,
-    WalletEventSender, WalletResult, WalletStorage,
+    events::{EventFilter, EventHandlers, EventReplay, InitEvents},
+    send_wallet_event, Cluster, Reflection, SendOptions, SentTransaction, SignInOutput,
+    SignedMessageOutput, SigninInput, Wallet, WalletAccount, WalletError, WalletEvent,
+    WalletEventFilter, WalletEventHandler, WalletEventReceiver, WalletEventSender,
+    WalletEventStream, WalletResult, WalletStorage, SOLANA_SIGN_IN_IDENTIFIER,
 };
 
 /// Contains the connected wallet and account.
@@ -19,6 +34,7 @@ use crate::{
 pub struct ConnectionInfo {
     wallet: Option<Wallet>,
     account: Option<WalletAccount>,
+    accounts: Vec<WalletAccount>,
     previous_accounts: Vec<WalletAccount>,
 }
 
@@ -35,8 +51,12 @@ impl ConnectionInfo {
         self
     }
 
-    /// Set the connected account
-    pub fn set_account(&mut self, account: WalletAccount) -> &mut Self {
+    /// Set the connected account. The time it was connected is recorded
+    /// if the account does not already have one
+    pub fn set_account(&mut self, mut account: WalletAccount) -> &mut Self {
+        if account.connected_at.is_none() {
+            account.set_connected_now();
+        }
         self.account.replace(account);
 
         self
@@ -46,19 +66,68 @@ impl ConnectionInfo {
     pub async fn connect(&mut self, sender: WalletEventSender) -> WalletResult<WalletAccount> {
         let wallet = self.connected_wallet()?;
 
-        let connected_account = wallet.features.connect.call_connect().await?;
+        let connected_accounts = wallet.features.connect.call_connect().await?;
+
+        Ok(self
+            .set_connected(connected_accounts, WalletEvent::Connected, sender)
+            .await)
+    }
 
+    /// Set the accounts returned by a successful connect request as the connected accounts,
+    /// with the first account as the connected account,
+    /// and send the `event` created from the account, either [WalletEvent::Connected]
+    /// or [WalletEvent::Reconnected]
+    async fn set_connected(
+        &mut self,
+        mut connected_accounts: Vec<WalletAccount>,
+        event: fn(WalletAccount) -> WalletEvent,
+        sender: WalletEventSender,
+    ) -> WalletAccount {
+        connected_accounts.iter_mut().for_each(|connected_account| {
+            connected_account.set_connected_now();
+        });
+
+        let connected_account = connected_accounts.remove(0); // Never empty
+        self.accounts = core::iter::once(connected_account.clone())
+            .chain(connected_accounts)
+            .collect();
         self.set_account(connected_account.clone());
 
-        send_wallet_event(WalletEvent::Connected(connected_account.clone()), sender).await;
+        send_wallet_event(event(connected_account.clone()), sender).await;
 
-        Ok(connected_account)
+        connected_account
+    }
+
+    /// Select the account with the `public_key` among the [connected accounts](Self::connected_accounts)
+    /// as the connected account and send a [WalletEvent::AccountChanged] event.
+    /// Returns [WalletError::ValueNotFound] if no connected account has the `public_key`
+    pub async fn switch_account(
+        &mut self,
+        public_key: &VerifyingKey,
+        sender: WalletEventSender,
+    ) -> WalletResult<()> {
+        let position = self
+            .accounts
+            .iter()
+            .position(|account| account.public_key == public_key.to_bytes())
+            .ok_or(WalletError::ValueNotFound)?;
+
+        let account = self.accounts.remove(position);
+        self.accounts.insert(0, account.clone());
+
+        self.push_previous_account();
+        self.set_account(account.clone());
+
+        send_wallet_event(WalletEvent::AccountChanged(account), sender).await;
+
+        Ok(())
     }
 
     /// Set the disconnected account
     pub async fn set_disconnected(&mut self, sender: WalletEventSender) -> &mut Self {
         self.wallet.take();
         self.account.take();
+        self.accounts.clear();
         self.previous_accounts.clear();
 
         send_wallet_event(WalletEvent::Disconnected, sender).await;
@@ -76,6 +145,31 @@ impl ConnectionInfo {
         self.account.as_ref().ok_or(WalletError::AccountNotFound)
     }
 
+    /// Get all the [accounts](WalletAccount) returned by the wallet when it was connected.
+    /// Some wallets return more than one account, the first account
+    /// is the one returned by [Self::connected_account]
+    pub fn connected_accounts(&self) -> &[WalletAccount] {
+        self.accounts.as_slice()
+    }
+
+    /// Get the connected [account](WalletAccount) if the wallet named `wallet_name`
+    /// is the connected wallet, otherwise `None`
+    pub fn connected_account_for(&self, wallet_name: &str) -> Option<&WalletAccount> {
+        self.wallet
+            .as_ref()
+            .filter(|wallet| wallet.name().as_bytes() == wallet_name.as_bytes())
+            .and(self.account.as_ref())
+    }
+
+    /// Check that a wallet and account are connected and that the connected wallet
+    /// is still responsive. See [WalletAdapter::health_check]
+    pub fn health_check(&self) -> WalletResult<()> {
+        match (self.wallet.as_ref(), self.account.as_ref()) {
+            (Some(wallet), Some(_)) => wallet.health_check(),
+            _ => Err(WalletError::NotConnected),
+        }
+    }
+
     /// Get the connected [wallet](Wallet) but return an [Option]
     /// to show the wallet exists instead of a [WalletResult]
     pub fn connected_wallet_raw(&self) -> Option<&Wallet> {
@@ -98,7 +192,9 @@ impl ConnectionInfo {
         match self.connected_wallet() {
             Ok(wallet) => {
                 let event_outcome = match account_processing {
-                    Some(connected_account) => {
+                    Some(mut connected_account) => {
+                        connected_account.set_connected_now();
+
                         if self.account.is_none()
                             && self.wallet.is_none()
                             && self.previous_accounts.is_empty()
@@ -172,6 +268,25 @@ impl ConnectionInfo {
 /// The [ConnectionInfo] wrapped in an `Arc<RwLock<T>>`
 pub type ConnectionInfoInner = Arc<RwLock<ConnectionInfo>>;
 
+/// The state of the connection of a [WalletAdapter] to a browser wallet.
+/// See [WalletAdapter::state]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConnectionState {
+    /// No wallet is connected
+    #[default]
+    Disconnected,
+    /// A connect request is waiting for the browser wallet to respond
+    Connecting,
+    /// A wallet and account are connected
+    Connected,
+    /// A disconnect request is waiting for the browser wallet to respond
+    Disconnecting,
+}
+
+/// The default number of events buffered in the channel of the [WalletAdapter].
+/// See [WalletAdapter::init_with_channel_capacity]
+pub const DEFAULT_EVENT_CHANNEL_CAPACITY: usize = 5;
+
 /// Operations on a browser window.
 /// `Window` and `Document` object must be present otherwise
 /// an error is thrown.
@@ -183,13 +298,21 @@ pub struct WalletAdapter {
     connection_info: ConnectionInfoInner,
     wallet_events: WalletEventReceiver,
     wallet_events_sender: WalletEventSender,
-    signal_receiver: Receiver<()>,
+    event_filter: EventFilter,
+    event_replay: EventReplay,
+    event_handlers: EventHandlers,
+    active_wallet: Option<Wallet>,
+    account_changes_unsubscribe: Option<Function>,
+    connection_state: Rc<Cell<ConnectionState>>,
+    require_user_gesture: bool,
+    pub(crate) auto_reconnect: Option<String>,
 }
 
 impl WalletAdapter {
     /// Get the `Window` and `Document` object in the current browser window,
     /// initialize the `AppReady` and `Register` events of the wallet standard
-    /// and creates a bounded channel with capacity default of 5 messages before capacity is filled.
+    /// and creates a bounded channel with capacity default of [DEFAULT_EVENT_CHANNEL_CAPACITY]
+    /// messages before capacity is filled.
     /// Use [WalletAdapter::init_with_channel_capacity] to initialize with a desired channel capacity.
     pub fn init() -> WalletResult<Self> {
         let window = if let Some(window) = web_sys::window() {
@@ -204,12 +327,23 @@ impl WalletAdapter {
             return Err(WalletError::MissingAccessToBrowserDocument);
         };
 
-        Self::init_with_channel_capacity_window_and_document(5, window, document)
+        Self::init_with_channel_capacity_window_and_document(
+            DEFAULT_EVENT_CHANNEL_CAPACITY,
+            window,
+            document,
+        )
     }
 
     /// Get the `Window` and `Document` object in the current browser window,
     /// initialize the `AppReady` and `Register` events of the wallet standard
     /// and creates a bounded channel with user-specified capacity.
+    ///
+    /// The `capacity` is the number of events buffered until the [WalletEventReceiver]
+    /// returned by [Self::events] is read. Once the buffer is full no events are dropped,
+    /// instead the background tasks sending the events wait until there is space in the buffer
+    /// so the events keep piling up in memory, for example in a backgrounded tab.
+    /// A small capacity keeps less events in memory while a large capacity
+    /// allows reading the events less often. A `capacity` of `0` is treated as `1`
     pub fn init_with_channel_capacity(capacity: usize) -> WalletResult<Self> {
         let window = if let Some(window) = web_sys::window() {
             window
@@ -228,16 +362,50 @@ impl WalletAdapter {
 
     /// Same as [WalletAdapter::init] but a `capacity` value
     /// can be passed to create an channel with a desired capacity
-    #[allow(clippy::arc_with_non_send_sync)]
     pub fn init_with_channel_capacity_window_and_document(
         capacity: usize,
         window: Window,
         document: Document,
     ) -> WalletResult<Self> {
-        let storage = WalletStorage::default();
+        Self::init_with_case_sensitive_names(capacity, window, document, false)
+    }
 
-        let (sender, receiver) = bounded::<WalletEvent>(capacity);
-        let (_, signal_receiver) = bounded::<()>(capacity);
+    /// Same as [WalletAdapter::init_with_channel_capacity_window_and_document]
+    /// but if `case_sensitive_names` is `true` the registered wallets are keyed
+    /// by the exact bytes of their name instead of their lowercase name.
+    /// This allows two wallets whose names only differ by case to be registered
+    /// but a wallet registering itself with a different casing of its name is no longer
+    /// deduplicated and [Self::get_wallet] requires the exact name.
+    /// See [WalletStorage::new]
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn init_with_case_sensitive_names(
+        capacity: usize,
+        window: Window,
+        document: Document,
+        case_sensitive_names: bool,
+    ) -> WalletResult<Self> {
+        let storage = WalletStorage::new(case_sensitive_names);
+
+        let capacity = capacity.max(1);
+        let (sender, internal_receiver) = bounded::<WalletEvent>(capacity);
+        let (filtered_sender, receiver) = bounded::<WalletEvent>(capacity);
+
+        let event_filter = EventFilter::default();
+        let event_replay = EventReplay::default();
+        let forward_filter = event_filter.clone();
+        let forward_replay = event_replay.clone();
+        let event_handlers = EventHandlers::default();
+        let forward_handlers = event_handlers.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            forward_filter
+                .forward(
+                    internal_receiver,
+                    filtered_sender,
+                    forward_replay,
+                    forward_handlers,
+                )
+                .await
+        });
 
         let mut new_self = Self {
             window: window.clone(),
@@ -246,7 +414,14 @@ impl WalletAdapter {
             connection_info: Arc::new(RwLock::new(ConnectionInfo::default())),
             wallet_events: receiver,
             wallet_events_sender: sender,
-            signal_receiver,
+            event_filter,
+            event_replay,
+            event_handlers,
+            active_wallet: None,
+            account_changes_unsubscribe: None,
+            connection_state: Rc::default(),
+            require_user_gesture: false,
+            auto_reconnect: None,
         };
 
         InitEvents::new(&window).init(&mut new_self)?;
@@ -258,7 +433,11 @@ impl WalletAdapter {
     /// initialized elsewhere. For example some Rust frontend frameworks already
     /// expose the window and document objects, you could pass them here.
     pub fn init_custom(window: Window, document: Document) -> WalletResult<Self> {
-        Self::init_with_channel_capacity_window_and_document(5, window, document)
+        Self::init_with_channel_capacity_window_and_document(
+            DEFAULT_EVENT_CHANNEL_CAPACITY,
+            window,
+            document,
+        )
     }
 
     /// Listen for [WalletEvent] to be notified when a wallet
@@ -268,38 +447,311 @@ impl WalletAdapter {
         self.wallet_events.clone()
     }
 
-    /// Send a connect request to the browser wallet
+    /// Get the events of [Self::events] as a [WalletEventStream]
+    pub fn event_stream(&self) -> WalletEventStream {
+        WalletEventStream::new(self.events())
+    }
+
+    /// Create a new [WalletEventReceiver] that first receives the last `replay` events
+    /// delivered to [Self::events] (at most [crate::EVENT_REPLAY_CAPACITY]) and then
+    /// a copy of every event delivered afterwards. This allows components that are
+    /// created after a wallet has connected to catch up on the [WalletEvent::Connected] event.
+    /// Unlike clones of the receiver returned by [Self::events], which compete for events,
+    /// each subscriber receives every event. Events are dropped for a subscriber whose
+    /// channel is full and a subscriber is removed once its receiver is dropped.
+    pub fn subscribe_with_replay(&self, replay: usize) -> WalletEventReceiver {
+        let capacity = self
+            .wallet_events
+            .capacity()
+            .unwrap_or(DEFAULT_EVENT_CHANNEL_CAPACITY);
+
+        self.event_replay.subscribe(replay, capacity)
+    }
+
+    /// Only deliver the [WalletEvent]s for which the `predicate` returns `true`
+    /// to the [WalletEventReceiver] returned by [Self::events].
+    /// Events failing the predicate are dropped. Passing `None` delivers all events.
+    pub fn set_event_filter(&mut self, predicate: Option<WalletEventFilter>) -> &mut Self {
+        self.event_filter.set(predicate);
+
+        self
+    }
+
+    /// Register a `handler` that is invoked for every [WalletEvent], as an alternative
+    /// to receiving the events from [Self::events]. This suits framework integrations
+    /// where a callback setting a reactive signal is more natural than a receiver loop.
+    /// The handlers are invoked before the predicate set using [Self::set_event_filter]
+    /// so a filter returning `false` for all events can be used to stop delivering
+    /// the events to the channel if only the handlers are used.
+    /// A handler must not register another handler
+    pub fn on(&mut self, handler: impl Fn(&WalletEvent) + 'static) -> &mut Self {
+        let handler: WalletEventHandler = Box::new(handler);
+        self.event_handlers.push(handler);
+
+        self
+    }
+
+    /// If `required` is `true` the connect requests that may open a wallet popup check
+    /// `navigator.userActivation.isActive` first and return [WalletError::UserGestureRequired]
+    /// if they are not made in response to a user gesture, since browsers block popups otherwise.
+    /// Useful during development to catch a silently blocked popup.
+    /// Browsers that do not implement `navigator.userActivation` are not checked
+    pub fn require_user_gesture(&mut self, required: bool) -> &mut Self {
+        self.require_user_gesture = required;
+
+        self
+    }
+
+    fn check_user_gesture(&self) -> WalletResult<()> {
+        if !self.require_user_gesture {
+            return Ok(());
+        }
+
+        match Reflection::new_from_path(
+            self.window.as_ref(),
+            &["navigator", "userActivation", "isActive"],
+        ) {
+            Ok(is_active) if is_active.get_inner().is_falsy() => {
+                Err(WalletError::UserGestureRequired)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn wallet_events_sender(&self) -> WalletEventSender {
+        self.wallet_events_sender.clone()
+    }
+
+    /// Send a connect request to the browser wallet.
+    /// If the same wallet is already connected the connected account is returned
+    /// without sending a new request to the browser wallet.
+    /// Use [Self::connect_with_force] to always send a connect request.
     pub async fn connect(&mut self, wallet: Wallet) -> WalletResult<WalletAccount> {
-        let wallet_name = wallet.name().to_string();
-        let sender = self.wallet_events_sender.clone();
-        let signal_receiver = self.signal_receiver.clone();
+        self.connect_with_force(wallet, false).await
+    }
 
-        if self.connection_info().await.connected_wallet().is_ok() {
-            let capacity = signal_receiver.capacity().unwrap_or(5);
-            let (_, signal_receiver) = bounded::<()>(capacity);
-            self.signal_receiver = signal_receiver;
+    /// Same as [Self::connect] but if `force` is `true` a connect request is sent
+    /// to the browser wallet even if the same wallet is already connected
+    pub async fn connect_with_force(
+        &mut self,
+        wallet: Wallet,
+        force: bool,
+    ) -> WalletResult<WalletAccount> {
+        if !force {
+            if let Some(connected_account) = self
+                .connection_info()
+                .await
+                .connected_account_for(wallet.name())
+            {
+                return Ok(connected_account.clone());
+            }
         }
 
+        self.check_user_gesture()?;
+        self.begin_connecting()?;
+
+        let outcome = match wallet.connect_all().await {
+            Ok(connected_accounts) => {
+                self.set_connected(wallet, connected_accounts, WalletEvent::Connected)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+
+        self.end_connection_request();
+
+        outcome
+    }
+
+    /// Reconnect to the registered wallet named `wallet_name`, for example on page load,
+    /// without forcing the user to approve the connection again.
+    /// A silent connect request (`standard:connect` with `silent: true`) is sent first,
+    /// which returns the accounts the app is already authorized to use without prompting the user.
+    /// Only if the wallet does not support silent connect, meaning it returns
+    /// [WalletError::SilentConnectUnsupported], a normal connect request is sent instead,
+    /// which prompts the user for approval and is subject to [Self::require_user_gesture].
+    /// Any other error of the silent connect request, for example a locked wallet,
+    /// a network failure or [WalletError::ConnectHasNoAccounts] when the user revoked access,
+    /// is returned instead of prompting the user so the app can show its connect button.
+    /// On success a [WalletEvent::Reconnected] event is sent instead of [WalletEvent::Connected]
+    /// and on failure a [WalletEvent::ReconnectFailed] event is sent before returning the error
+    pub async fn reconnect(&mut self, wallet_name: &str) -> WalletResult<WalletAccount> {
+        let outcome = match self.begin_connecting() {
+            Ok(()) => {
+                let outcome = self.try_reconnect(wallet_name).await;
+                self.end_connection_request();
+
+                outcome
+            }
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = outcome.as_ref() {
+            send_wallet_event(
+                WalletEvent::ReconnectFailed(error.clone()),
+                self.wallet_events_sender.clone(),
+            )
+            .await;
+        }
+
+        outcome
+    }
+
+    async fn try_reconnect(&mut self, wallet_name: &str) -> WalletResult<WalletAccount> {
+        let wallet = self.get_wallet(wallet_name)?;
+
+        let connected_accounts = match wallet.connect_silent().await {
+            Ok(connected_accounts) => connected_accounts,
+            Err(WalletError::SilentConnectUnsupported(_)) => {
+                self.check_user_gesture()?;
+
+                wallet.connect_all().await?
+            }
+            Err(error) => return Err(error),
+        };
+
+        self.set_connected(wallet, connected_accounts, WalletEvent::Reconnected)
+            .await
+    }
+
+    /// Reconnect to the wallet configured using [crate::WalletAdapterBuilder::auto_reconnect]
+    /// as described in [Self::reconnect]. Call this once after building the adapter,
+    /// for example on page load.
+    /// Returns `None` if no wallet was configured
+    pub async fn auto_reconnect(&mut self) -> WalletResult<Option<WalletAccount>> {
+        match self.auto_reconnect.clone() {
+            Some(wallet_name) => self.reconnect(&wallet_name).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Send a connect request to the active wallet selected using [Self::set_active_wallet]
+    /// and return [WalletError::Timeout] if the browser wallet does not respond within `millis`
+    /// milliseconds, for example when the user closes the approval popup and the wallet never
+    /// resolves the request. The state of the adapter is only updated if the wallet responds
+    /// in time so a late response after the timeout is ignored.
+    /// Returns [WalletError::WalletNotFound] if no wallet has been selected
+    pub async fn connect_with_timeout(&mut self, millis: u32) -> WalletResult<WalletAccount> {
+        let wallet = self
+            .active_wallet
+            .clone()
+            .ok_or(WalletError::WalletNotFound)?;
+
+        self.check_user_gesture()?;
+        self.begin_connecting()?;
+        let outcome = self.connect_with_timeout_inner(wallet, millis).await;
+        self.end_connection_request();
+
+        outcome
+    }
+
+    async fn connect_with_timeout_inner(
+        &mut self,
+        wallet: Wallet,
+        millis: u32,
+    ) -> WalletResult<WalletAccount> {
+        let mut timeout_handle = Ok(0);
+        let timeout = Promise::new(&mut |resolve, _reject| {
+            timeout_handle = self
+                .window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    &resolve,
+                    millis.min(i32::MAX as u32) as i32,
+                );
+        });
+        let timeout_handle = timeout_handle?;
+
+        // The connect request is dropped if the timeout fires first
+        let outcome = {
+            let mut connect = pin!(wallet.connect_all());
+            let mut timeout = pin!(JsFuture::from(timeout));
+
+            poll_fn(|context| {
+                if let Poll::Ready(outcome) = connect.as_mut().poll(context) {
+                    Poll::Ready(outcome)
+                } else if timeout.as_mut().poll(context).is_ready() {
+                    Poll::Ready(Err(WalletError::Timeout(millis)))
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await
+        };
+
+        self.window.clear_timeout_with_handle(timeout_handle);
+
+        self.set_connected(wallet, outcome?, WalletEvent::Connected)
+            .await
+    }
+
+    /// Set the `wallet` and the accounts returned by a successful connect request
+    /// as the connected wallet and accounts and listen for the account changes of the wallet
+    /// if it supports `standard:events`. The listener of a previously connected wallet is removed
+    async fn set_connected(
+        &mut self,
+        wallet: Wallet,
+        connected_accounts: Vec<WalletAccount>,
+        event: fn(WalletAccount) -> WalletEvent,
+    ) -> WalletResult<WalletAccount> {
+        let sender = self.wallet_events_sender.clone();
+        let standard_events = wallet.standard_events();
+
         let wallet_account = self
             .connection_info
             .write()
             .await
             .set_wallet(wallet)
-            .connect(sender.clone())
-            .await?;
+            .set_connected(connected_accounts, event, sender)
+            .await;
 
-        self.connection_info()
-            .await
-            .connected_wallet()?
+        if standard_events {
+            self.subscribe_account_changes().await?;
+        } else {
+            self.unsubscribe_account_changes()?;
+        }
+
+        Ok(wallet_account)
+    }
+
+    /// Register a `change` listener on the connected wallet using the `on` function
+    /// of its `standard:events` feature. The accounts of each change are sent on the
+    /// events channel as a [WalletEvent::Connected], [WalletEvent::AccountChanged]
+    /// or [WalletEvent::Disconnected] event.
+    /// This is called by the connect methods of [WalletAdapter] for wallets
+    /// supporting `standard:events`. The unsubscribe function returned by the wallet
+    /// is stored and called by [Self::unsubscribe_account_changes].
+    /// A listener registered previously, for example on a wallet connected before,
+    /// is removed first so that it does not send duplicate events
+    pub async fn subscribe_account_changes(&mut self) -> WalletResult<()> {
+        let wallet = self.connection_info().await.connected_wallet()?.clone();
+
+        if !wallet.standard_events() {
+            return Err(WalletError::MissingStandardEventsFunction);
+        }
+
+        self.unsubscribe_account_changes()?;
+
+        let unsubscribe = wallet
             .call_on_event(
                 self.connection_info.clone(),
-                wallet_name,
-                sender,
-                signal_receiver,
+                wallet.name().to_string(),
+                self.wallet_events_sender.clone(),
             )
             .await?;
+        self.account_changes_unsubscribe.replace(unsubscribe);
 
-        Ok(wallet_account)
+        Ok(())
+    }
+
+    /// Remove the `change` listener registered by [Self::subscribe_account_changes]
+    /// by calling the unsubscribe function returned by `[standard:events].on`.
+    /// Does nothing if no listener is registered
+    pub fn unsubscribe_account_changes(&mut self) -> WalletResult<()> {
+        if let Some(unsubscribe) = self.account_changes_unsubscribe.take() {
+            unsubscribe.call0(&JsValue::null())?;
+        }
+
+        Ok(())
     }
 
     /// Lookup a wallet entry by name from the registered wallets
@@ -310,16 +762,60 @@ impl WalletAdapter {
         self.connect(wallet).await
     }
 
-    /// Send a disconnect request to the browser wallet
-    pub async fn disconnect(&mut self) {
+    /// Select the registered wallet named `wallet_name` as the active wallet
+    /// targeted by [Self::connect_active_wallet].
+    /// Returns [WalletError::WalletNotFound] if no wallet with that name is registered
+    pub fn set_active_wallet(&mut self, wallet_name: &str) -> WalletResult<()> {
+        let wallet = self.get_wallet(wallet_name)?;
+        self.active_wallet.replace(wallet);
+
+        Ok(())
+    }
+
+    /// Get the wallet selected using [Self::set_active_wallet]
+    pub fn active_wallet(&self) -> Option<&Wallet> {
+        self.active_wallet.as_ref()
+    }
+
+    /// Send a connect request to the active wallet selected using [Self::set_active_wallet].
+    /// Returns [WalletError::WalletNotFound] if no wallet has been selected
+    pub async fn connect_active_wallet(&mut self) -> WalletResult<WalletAccount> {
+        let wallet = self
+            .active_wallet
+            .clone()
+            .ok_or(WalletError::WalletNotFound)?;
+
+        self.connect(wallet).await
+    }
+
+    /// Send a disconnect request to the browser wallet using its `standard:disconnect` feature,
+    /// clear the connected wallet and account and send a [WalletEvent::Disconnected] event.
+    /// Not all wallets implement `standard:disconnect` so if the connected wallet does not
+    /// support it only the local state is cleared. The local state is also cleared
+    /// and the `change` listener registered by [Self::subscribe_account_changes] is removed
+    /// if the browser wallet returns an error, which is then returned
+    pub async fn disconnect(&mut self) -> WalletResult<()> {
         let sender = self.wallet_events_sender.clone();
 
+        self.connection_state.set(ConnectionState::Disconnecting);
+
+        let connected_wallet = self.connection_info().await.connected_wallet_raw().cloned();
+        let outcome = match connected_wallet {
+            Some(wallet) if wallet.standard_disconnect() => wallet.disconnect().await,
+            _ => Ok(()),
+        };
+
         self.connection_info
             .write()
             .await
             .set_disconnected(sender)
             .await;
-        self.signal_receiver.close();
+
+        self.end_connection_request();
+
+        let unsubscribe_outcome = self.unsubscribe_account_changes();
+
+        outcome.and(unsubscribe_outcome)
     }
 
     /// Send a sign in request to the browser wallet to Sign In With Solana
@@ -335,6 +831,42 @@ impl WalletAdapter {
             .await
     }
 
+    /// One-click Sign In With Solana using the `solana:signIn` feature of the registered
+    /// wallet named `wallet_name`, without connecting to the wallet first.
+    /// The wallet returns the signed message, the signature and the account in one call.
+    /// On success the returned account is set as the connected account
+    /// and a [WalletEvent::Connected] event is sent.
+    /// Returns [WalletError::UnsupportedFeature] if the wallet does not support `solana:signIn`
+    /// in which case the app can fall back to [Self::connect_by_name] and [Self::sign_message]
+    pub async fn sign_in_and_connect(
+        &mut self,
+        wallet_name: &str,
+        signin_input: &SigninInput,
+    ) -> WalletResult<SignInOutput> {
+        let wallet = self.get_wallet(wallet_name)?;
+
+        if !wallet.solana_signin() {
+            return Err(WalletError::UnsupportedFeature(
+                SOLANA_SIGN_IN_IDENTIFIER.to_string(),
+            ));
+        }
+
+        self.check_user_gesture()?;
+        self.begin_connecting()?;
+
+        let outcome = match wallet.sign_in_with_account(signin_input).await {
+            Ok(output) => self
+                .set_connected(wallet, vec![output.account.clone()], WalletEvent::Connected)
+                .await
+                .map(|_| output),
+            Err(error) => Err(error),
+        };
+
+        self.end_connection_request();
+
+        outcome
+    }
+
     /// Send a sign and send transaction request to the browser wallet
     pub async fn sign_and_send_transaction(
         &self,
@@ -356,6 +888,41 @@ impl WalletAdapter {
             .await
     }
 
+    /// Sign and send each of the `transactions` in order using the default [SendOptions].
+    /// Each transaction is processed even if a previous one failed so the outcome
+    /// of every transaction is returned in the same order as the `transactions`
+    pub async fn sign_and_send_all(
+        &self,
+        transactions: Vec<Vec<u8>>,
+        cluster: Cluster,
+    ) -> Vec<WalletResult<SentTransaction>> {
+        Self::send_each(transactions, |transaction_bytes| async move {
+            self.sign_and_send_transaction(&transaction_bytes, cluster, SendOptions::default())
+                .await
+        })
+        .await
+    }
+
+    async fn send_each<F, Fut>(
+        transactions: Vec<Vec<u8>>,
+        mut send: F,
+    ) -> Vec<WalletResult<SentTransaction>>
+    where
+        F: FnMut(Vec<u8>) -> Fut,
+        Fut: Future<Output = WalletResult<Signature>>,
+    {
+        let mut outcomes = Vec::with_capacity(transactions.len());
+
+        for (index, transaction_bytes) in transactions.into_iter().enumerate() {
+            let outcome = send(transaction_bytes)
+                .await
+                .map(|signature| SentTransaction::new(index, signature));
+            outcomes.push(outcome);
+        }
+
+        outcomes
+    }
+
     /// Send a connect request to the browser wallet
     pub async fn sign_transaction(
         &self,
@@ -389,6 +956,49 @@ impl WalletAdapter {
             .await
     }
 
+    /// The current [ConnectionState] of the adapter. Clones of the adapter share the same state.
+    /// Outside of a connect or disconnect request the state reflects whether an account
+    /// is connected, including when the browser wallet removes all accounts on its own
+    pub fn state(&self) -> ConnectionState {
+        match self.connection_state.get() {
+            settled @ (ConnectionState::Disconnected | ConnectionState::Connected) => self
+                .connection_info
+                .try_read()
+                .map(|connection_info| {
+                    if connection_info.account.is_some() {
+                        ConnectionState::Connected
+                    } else {
+                        ConnectionState::Disconnected
+                    }
+                })
+                .unwrap_or(settled),
+            pending => pending,
+        }
+    }
+
+    /// Check if a connect request is waiting for the browser wallet to respond
+    pub fn is_connecting(&self) -> bool {
+        self.state() == ConnectionState::Connecting
+    }
+
+    /// Mark a connect request as pending. Returns [WalletError::ConnectionInProgress]
+    /// if one is already pending so that the wallet does not open a second approval popup
+    fn begin_connecting(&self) -> WalletResult<()> {
+        if self.is_connecting() {
+            return Err(WalletError::ConnectionInProgress);
+        }
+
+        self.connection_state.set(ConnectionState::Connecting);
+
+        Ok(())
+    }
+
+    /// Settle the state after a connect or disconnect request,
+    /// [Self::state] then reads it from the connection info
+    fn end_connection_request(&self) {
+        self.connection_state.set(ConnectionState::Disconnected);
+    }
+
     /// Check if an [account](WalletAccount) is connected
     pub async fn is_connected(&self) -> bool {
         self.connection_info
@@ -399,6 +1009,47 @@ impl WalletAdapter {
             .is_some()
     }
 
+    /// A lightweight probe of the connected wallet for use in status indicators.
+    /// No request is sent to the browser wallet, instead the callbacks of the features
+    /// the wallet supports are checked to still be callable.
+    /// Returns [WalletError::NotConnected] if no wallet is connected and
+    /// [WalletError::WalletUnresponsive] if the handle to the wallet is dead
+    pub async fn health_check(&self) -> WalletResult<()> {
+        self.connection_info().await.health_check()
+    }
+
+    /// Get the cached connected [account](WalletAccount) without sending
+    /// a connect request to the browser wallet, for example to check the connection
+    /// when the app regains focus. Returns `None` if no connect request has succeeded
+    /// or after the wallet was disconnected either by [Self::disconnect] or by
+    /// a [WalletEvent::Disconnected] event emitted by the browser wallet.
+    /// The account is cloned since the [ConnectionInfo] is behind an async [RwLock].
+    pub async fn connected_account(&self) -> Option<WalletAccount> {
+        self.connection_info()
+            .await
+            .connected_account_raw()
+            .cloned()
+    }
+
+    /// Select which of the [connected accounts](Self::connected_accounts) is the connected account
+    /// without reconnecting and send a [WalletEvent::AccountChanged] event.
+    /// Returns [WalletError::ValueNotFound] if no connected account has the `public_key`
+    pub async fn switch_account(&mut self, public_key: &VerifyingKey) -> WalletResult<()> {
+        let sender = self.wallet_events_sender.clone();
+
+        self.connection_info
+            .write()
+            .await
+            .switch_account(public_key, sender)
+            .await
+    }
+
+    /// Get all the accounts returned by the connected wallet.
+    /// See [ConnectionInfo::connected_accounts]
+    pub async fn connected_accounts(&self) -> Vec<WalletAccount> {
+        self.connection_info().await.connected_accounts().to_vec()
+    }
+
     /// Get the connected [ConnectionInfo] containing the
     /// [account](WalletAccount) and [wallet](Wallet)
     pub async fn connection_info(&self) -> async_lock::RwLockReadGuard<'_, ConnectionInfo> {
@@ -425,6 +1076,12 @@ impl WalletAdapter {
         self.storage.borrow()
     }
 
+    /// Get the storage where the adapter stores the registered wallets as mutable,
+    /// for example to remove a wallet using [WalletStorage::remove]
+    pub fn storage_mut(&mut self) -> &mut WalletStorage {
+        &mut self.storage
+    }
+
     /// Get the clusters supported by the connected wallet
     pub async fn clusters(&self) -> WalletResult<Vec<Cluster>> {
         let mut clusters = Vec::<Cluster>::default();
@@ -450,6 +1107,16 @@ impl WalletAdapter {
         self.storage.borrow().get_wallets()
     }
 
+    /// Get the registered wallets that advertise the `feature`, for example
+    /// `solana:signAndSendTransaction`, to present only the wallets usable in a certain context.
+    /// See [Wallet::supports_feature]
+    pub fn wallets_supporting(&self, feature: &str) -> Vec<Wallet> {
+        self.wallets()
+            .into_iter()
+            .filter(|wallet| wallet.supports_feature(feature))
+            .collect()
+    }
+
     /// Get a certain wallet by its name
     pub fn get_wallet(&self, wallet_name: &str) -> WalletResult<Wallet> {
         self.storage
@@ -549,3 +1216,243 @@ impl PartialEq for WalletAdapter {
     }
 }
 impl Eq for WalletAdapter {}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod adapter_tests {
+    use super::*;
+    use crate::{
+        events::event_filter_tests::block_on, Connect, MockWallet, Reflection, SemverVersion,
+        StandardEvents,
+    };
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn connected_account_for() {
+        let mut connection_info = ConnectionInfo::new();
+        assert!(connection_info.connected_account_for("").is_none());
+
+        connection_info
+            .set_wallet(Wallet::default())
+            .set_account(WalletAccount::default());
+
+        let first = connection_info.connected_account_for("").cloned();
+        let second = connection_info.connected_account_for("").cloned();
+
+        assert!(first.is_some());
+        assert_eq!(first, second);
+        assert!(connection_info.connected_account_for("Phantom").is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn disconnected_event_clears_account() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+
+        let mut connection_info = ConnectionInfo::new();
+        connection_info
+            .set_wallet(Wallet::default())
+            .set_account(WalletAccount::default());
+        assert!(connection_info.connected_account_raw().is_some());
+
+        block_on(connection_info.emit_wallet_event("", None, sender));
+
+        assert_eq!(receiver.try_recv(), Ok(WalletEvent::Disconnected));
+        assert!(connection_info.connected_account_raw().is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn connected_accounts() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+
+        let primary_key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]).verifying_key();
+        let secondary_key = ed25519_dalek::SigningKey::from_bytes(&[2u8; 32]).verifying_key();
+
+        let mut primary = WalletAccount::default();
+        primary.public_key = primary_key.to_bytes();
+        let mut secondary = WalletAccount::default();
+        secondary.public_key = secondary_key.to_bytes();
+
+        let mut connection_info = ConnectionInfo::new();
+        let connected_account = block_on(connection_info.set_connected(
+            vec![primary, secondary],
+            WalletEvent::Connected,
+            sender.clone(),
+        ));
+
+        assert_eq!(connected_account.public_key(), primary_key.to_bytes());
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(WalletEvent::Connected(connected_account.clone()))
+        );
+        assert_eq!(
+            connection_info.connected_account_raw(),
+            Some(&connected_account)
+        );
+        assert_eq!(connection_info.connected_accounts().len(), 2);
+
+        block_on(connection_info.switch_account(&secondary_key, sender.clone())).unwrap();
+        let switched_account = connection_info.connected_account_raw().cloned().unwrap();
+        assert_eq!(switched_account.public_key(), secondary_key.to_bytes());
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(WalletEvent::AccountChanged(switched_account.clone()))
+        );
+        assert_eq!(connection_info.connected_accounts()[0], switched_account);
+
+        let unknown_key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        assert_eq!(
+            block_on(connection_info.switch_account(&unknown_key, sender.clone())),
+            Err(WalletError::ValueNotFound)
+        );
+
+        block_on(connection_info.set_disconnected(sender));
+        assert!(connection_info.connected_accounts().is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn health_check() {
+        let mut connection_info = ConnectionInfo::new();
+        assert_eq!(
+            connection_info.health_check(),
+            Err(WalletError::NotConnected)
+        );
+
+        connection_info
+            .set_wallet(MockWallet::new("Mock").to_wallet().unwrap())
+            .set_account(WalletAccount::default());
+        assert_eq!(connection_info.health_check(), Ok(()));
+
+        let mut nulled_wallet = MockWallet::new("Mock").to_wallet().unwrap();
+        nulled_wallet.features.connect = Connect::default();
+        connection_info.set_wallet(nulled_wallet);
+        assert_eq!(
+            connection_info.health_check(),
+            Err(WalletError::WalletUnresponsive("Mock".to_string()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn subscribe_with_replay() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let events = adapter.events();
+
+        let wallet = MockWallet::new("Mock Replay").to_wallet().unwrap();
+        let connected_account = adapter.connect(wallet).await.unwrap();
+        assert_eq!(
+            events.recv().await,
+            Ok(WalletEvent::Connected(connected_account.clone()))
+        );
+
+        let subscriber = adapter.subscribe_with_replay(5);
+        assert_eq!(
+            subscriber.try_recv(),
+            Ok(WalletEvent::Connected(connected_account))
+        );
+        assert!(subscriber.try_recv().is_err());
+        assert!(adapter.subscribe_with_replay(0).try_recv().is_err());
+
+        adapter.disconnect().await.unwrap();
+        assert_eq!(subscriber.recv().await, Ok(WalletEvent::Disconnected));
+    }
+
+    #[wasm_bindgen_test]
+    fn sign_and_send_all_isolates_failures() {
+        let transactions = vec![vec![0u8], vec![1u8], vec![2u8]];
+
+        let outcomes = block_on(WalletAdapter::send_each(
+            transactions,
+            |transaction_bytes| async move {
+                if transaction_bytes[0] == 1 {
+                    Err(WalletError::InvalidSignature)
+                } else {
+                    Ok(Signature::from_bytes(&[transaction_bytes[0]; 64]))
+                }
+            },
+        ));
+
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(
+            outcomes[0],
+            Ok(SentTransaction::new(0, Signature::from_bytes(&[0u8; 64])))
+        );
+        assert_eq!(outcomes[1], Err(WalletError::InvalidSignature));
+        assert_eq!(
+            outcomes[2],
+            Ok(SentTransaction::new(2, Signature::from_bytes(&[2u8; 64])))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn subscribe_account_changes_returns_unsubscribe() {
+        use web_sys::wasm_bindgen::prelude::Closure;
+
+        let mut events = Reflection::new_object();
+        events
+            .set_object(
+                &"on".into(),
+                &Closure::<dyn Fn(JsValue, JsValue) -> JsValue>::new(|_, _| {
+                    Closure::<dyn Fn()>::new(|| {}).into_js_value()
+                })
+                .into_js_value(),
+            )
+            .unwrap();
+        let events = StandardEvents::new(&events, SemverVersion::default()).unwrap();
+
+        let (sender, _receiver) = bounded::<WalletEvent>(5);
+        let unsubscribe = block_on(events.call_on_event(
+            Arc::new(RwLock::new(ConnectionInfo::default())),
+            "Mock".to_string(),
+            sender,
+        ))
+        .unwrap();
+        assert!(unsubscribe.call0(&JsValue::null()).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn reconnect_without_prompting() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let window = adapter.window().clone();
+        InitEvents::new(&window).force_reinit(&mut adapter).unwrap();
+        let events = adapter.events();
+
+        let mut mock_wallet = MockWallet::new("Mock Locked");
+        mock_wallet.set_silent_connect_rejection(Some("Wallet locked"));
+        mock_wallet.register(&window).unwrap();
+
+        let outcome = adapter.reconnect("Mock Locked").await;
+        assert!(matches!(outcome, Err(WalletError::WalletConnectError(_))));
+        assert_eq!(mock_wallet.connect_calls(), 1);
+        assert_eq!(
+            events.recv().await,
+            Ok(WalletEvent::ReconnectFailed(outcome.unwrap_err()))
+        );
+        assert!(!adapter.is_connected().await);
+
+        let mock_wallet = MockWallet::new("Mock Silent");
+        mock_wallet.register(&window).unwrap();
+
+        let connected_account = adapter.reconnect("Mock Silent").await.unwrap();
+        assert_eq!(mock_wallet.connect_calls(), 1);
+        assert_eq!(
+            events.recv().await,
+            Ok(WalletEvent::Reconnected(connected_account))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn connect_while_connecting() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        assert_eq!(adapter.state(), ConnectionState::Disconnected);
+        assert!(!adapter.is_connecting());
+
+        adapter.connection_state.set(ConnectionState::Connecting);
+        assert!(adapter.clone().is_connecting());
+
+        let wallet = MockWallet::new("Mock").to_wallet().unwrap();
+        assert_eq!(
+            block_on(adapter.connect(wallet)),
+            Err(WalletError::ConnectionInProgress)
+        );
+        assert!(adapter.is_connecting());
+    }
+}
@@ -0,0 +1,99 @@
+use web_sys::{Document, Window};
+
+use crate::{WalletAdapter, WalletError, WalletResult, DEFAULT_EVENT_CHANNEL_CAPACITY};
+
+/// Configure and build a [WalletAdapter].
+/// Options that are not set use the same defaults as [WalletAdapter::init].
+/// #### Example
+/// ```rust,no_run
+/// use wallet_adapter::WalletAdapterBuilder;
+///
+/// let adapter = WalletAdapterBuilder::new()
+///     .event_capacity(10)
+///     .auto_reconnect("Phantom")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WalletAdapterBuilder {
+    event_capacity: Option<usize>,
+    case_sensitive_names: bool,
+    auto_reconnect: Option<String>,
+    window: Option<Window>,
+    document: Option<Document>,
+}
+
+impl WalletAdapterBuilder {
+    /// Create a new builder with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of events buffered in the channel of the [WalletAdapter].
+    /// Defaults to [DEFAULT_EVENT_CHANNEL_CAPACITY].
+    /// See [WalletAdapter::init_with_channel_capacity]
+    pub fn event_capacity(&mut self, capacity: usize) -> &mut Self {
+        self.event_capacity.replace(capacity);
+
+        self
+    }
+
+    /// Key the registered wallets by the exact bytes of their name.
+    /// See [WalletAdapter::init_with_case_sensitive_names]
+    pub fn case_sensitive_names(&mut self, case_sensitive_names: bool) -> &mut Self {
+        self.case_sensitive_names = case_sensitive_names;
+
+        self
+    }
+
+    /// The name of the wallet that [WalletAdapter::auto_reconnect] reconnects to,
+    /// for example the wallet the user connected to in a previous session
+    pub fn auto_reconnect(&mut self, wallet_name: &str) -> &mut Self {
+        self.auto_reconnect.replace(wallet_name.to_string());
+
+        self
+    }
+
+    /// Use a [Window] initialized elsewhere instead of the window of the current browser
+    /// context, for example one exposed by a Rust frontend framework
+    pub fn window(&mut self, window: Window) -> &mut Self {
+        self.window.replace(window);
+
+        self
+    }
+
+    /// Use a [Document] initialized elsewhere instead of the document of the window
+    pub fn document(&mut self, document: Document) -> &mut Self {
+        self.document.replace(document);
+
+        self
+    }
+
+    /// Build the [WalletAdapter], initializing the `AppReady` and `Register` events
+    /// of the wallet standard.
+    /// Returns an error if the browser window or document cannot be accessed
+    pub fn build(&self) -> WalletResult<WalletAdapter> {
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => web_sys::window().ok_or(WalletError::MissingAccessToBrowserWindow)?,
+        };
+
+        let document = match self.document.clone() {
+            Some(document) => document,
+            None => window
+                .document()
+                .ok_or(WalletError::MissingAccessToBrowserDocument)?,
+        };
+
+        let mut adapter = WalletAdapter::init_with_case_sensitive_names(
+            self.event_capacity
+                .unwrap_or(DEFAULT_EVENT_CHANNEL_CAPACITY),
+            window,
+            document,
+            self.case_sensitive_names,
+        )?;
+        adapter.auto_reconnect = self.auto_reconnect.clone();
+
+        Ok(adapter)
+    }
+}
@@ -17,3 +17,8 @@ pub const SOLANA_SIGN_TRANSACTION_IDENTIFIER: &str = "solana:signTransaction";
 pub const SOLANA_SIGN_IN_IDENTIFIER: &str = "solana:signIn";
 /// Identifier for `solana:signMessage`
 pub const SOLANA_SIGN_MESSAGE_IDENTIFIER: &str = "solana:signMessage";
+
+/// Base58 address of the SPL Token program
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+/// Base58 address of the SPL Associated Token Account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hZZbsiqW5xWH25efTNsLJA8knL";
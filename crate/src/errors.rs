@@ -1,11 +1,17 @@
 use thiserror::Error;
-use web_sys::js_sys::{wasm_bindgen::JsValue, Reflect};
+#[cfg(feature = "browser")]
+use web_sys::{
+    js_sys::{self, Reflect},
+    wasm_bindgen::{JsCast, JsValue},
+};
 
+#[cfg(feature = "browser")]
 use crate::WalletEvent;
 
 /// A Result<T, WalletError>
 pub type WalletResult<T> = Result<T, WalletError>;
 
+#[cfg(feature = "browser")]
 impl From<async_channel::SendError<WalletEvent>> for WalletError {
     fn from(value: async_channel::SendError<WalletEvent>) -> Self {
         match value {
@@ -14,13 +20,48 @@ impl From<async_channel::SendError<WalletEvent>> for WalletError {
     }
 }
 
+impl From<bs58::decode::Error> for WalletError {
+    fn from(value: bs58::decode::Error) -> Self {
+        Self::Base58Decode(value.to_string())
+    }
+}
+
+/// The underlying [ed25519_dalek::SignatureError] of a [WalletError::Ed25519],
+/// available as its [source](std::error::Error::source).
+/// Only the message of the error is kept since [ed25519_dalek::SignatureError]
+/// does not implement `Clone`, `Eq`, `Ord` or `Hash` which [WalletError] derives
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Error)]
+#[error("{0}")]
+pub struct Ed25519Error(String);
+
+impl Ed25519Error {
+    /// The message of the underlying [ed25519_dalek::SignatureError]
+    pub fn message(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<ed25519_dalek::SignatureError> for Ed25519Error {
+    fn from(value: ed25519_dalek::SignatureError) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// Converts to [WalletError::Ed25519] keeping the underlying error as its source
+/// so that key parsing and signature verification can use `?`
+impl From<ed25519_dalek::SignatureError> for WalletError {
+    fn from(value: ed25519_dalek::SignatureError) -> Self {
+        Self::Ed25519(value.into())
+    }
+}
+
 /// Error handling enum
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Error)]
 pub enum WalletError {
-    /// Unable to send the a [WalletEvent] via the [crate::WalletEventSender]
+    /// Unable to send the a `WalletEvent` via the `WalletEventSender`
     #[error("Unable to send the a `WalletEvent` variant via the WalletEventSender channel")]
     ChannelError,
-    /// An JavaScript Error corresponding to a [JsValue] .
+    /// An JavaScript Error corresponding to a `JsValue` .
     /// It contains the error type represented by `name`,
     /// the error message `message`
     /// and the `stack` message which offers a trace of which functions were called.
@@ -34,9 +75,9 @@ pub enum WalletError {
         /// The stack from the JavaScript error message
         stack: String,
     },
-    /// An internal error that occurs when casting a JavaScript types or DOM types to a Rust type using [web_sys::wasm_bindgen::JsCast] trait
+    /// An internal error that occurs when casting a JavaScript types or DOM types to a Rust type using `web_sys::wasm_bindgen::JsCast` trait
     /// and `foo.dyn_ref::<T>()` or `foo.dyn_into::<T>()` where `foo`
-    /// is a variable of type [JsValue]. These error can also occur when trying to parse a browser error from JavaScript value
+    /// is a variable of type `JsValue`. These error can also occur when trying to parse a browser error from JavaScript value
     /// into a `WalletError::JsError`. Open an issue describing this error at -
     /// [https://github.com/JamiiDao/SolanaWalletAdapter/issues](https://github.com/JamiiDao/SolanaWalletAdapter/issues)
     #[error("Internal Error `{0}` occurred, this is a bug in the library please open an issue at https://github.com/JamiiDao/SolanaWalletAdapter/issues")]
@@ -84,23 +125,62 @@ pub enum WalletError {
     /// The blockchain encountered is not supported.
     #[error("The blockchain `{0}` is not supported")]
     UnsupportedChain(String),
+    /// The wallet does not advertise the feature required by the request.
+    /// Contains the feature identifier like `solana:signIn`
+    #[error("The wallet does not support the feature `{0}`")]
+    UnsupportedFeature(String),
+    /// The element at the index of an array of public keys is not 32 bytes
+    /// or not a valid Ed25519 public key
+    #[error("The public key at index `{0}` is not a valid Ed25519 public key")]
+    InvalidPublicKeyAtIndex(usize),
+    /// The value a wallet registered itself with is missing a required field or
+    /// contains a field that cannot be parsed. Contains the reason
+    #[error("The registered wallet is malformed: {0}")]
+    MalformedWallet(String),
     /// The `connect` function of the `standard:connect` namespace was not found while parsing a wallet
     #[error("The `connect` function of the `standard:connect` namespace was not found while parsing a wallet")]
     MissingConnectFunction,
     /// Attempted to connect to a wallet that does not exist or is yet to be registered
     #[error("Attempted to connect to a wallet that does not exist or is yet to be registered")]
     WalletNotFound,
+    /// Two different builds of a wallet registered using the same name
+    /// and the `previous` entry was replaced by the `replacement`
+    /// since the `replacement` advertises more features
+    #[error("The wallet `{previous}` was replaced by `{replacement}` which registered using the same name")]
+    WalletRegistrationConflict {
+        /// The name of the wallet that was registered first
+        previous: String,
+        /// The name of the wallet that replaced it
+        replacement: String,
+    },
+    /// No wallet and account are connected
+    #[error("No wallet and account are connected")]
+    NotConnected,
+    /// The callbacks of the connected wallet are no longer callable
+    #[error("The wallet `{0}` is unresponsive")]
+    WalletUnresponsive(String),
     /// Attempted to connect to an account that does not exist or might have been disconnected
     #[error(
         "Attempted to connect to an account that does not exist or might have been disconnected"
     )]
     AccountNotFound,
+    /// The browser wallet did not respond within the time limit.
+    /// Contains the time limit in milliseconds
+    #[error("The browser wallet did not respond within {0} milliseconds")]
+    Timeout(u32),
+    /// A connect request is already waiting for the browser wallet to respond
+    #[error("A connect request is already in progress")]
+    ConnectionInProgress,
     /// Unable to connect to a wallet. The user may have rejected the request
     #[error("Unable to connect to a wallet. Error `{0}` request")]
     WalletConnectError(String),
     /// The connect method did not return any accounts
     #[error("The connect method did not return any accounts")]
     ConnectHasNoAccounts,
+    /// The wallet does not support the `silent` input of `standard:connect`.
+    /// The wallet threw synchronously or with a `TypeError` when called with it
+    #[error("The wallet does not support silent connect. Error `{0}`")]
+    SilentConnectUnsupported(String),
     /// The wallet `standard:disconnect` feature is missing
     #[error("The wallet `standard:disconnect` feature is missing")]
     MissingDisconnectFunction,
@@ -137,6 +217,15 @@ pub enum WalletError {
     /// Invalid Base58 Address
     #[error("Invalid Base58 Address")]
     InvalidBase58Address,
+    /// The string is not a valid hex encoded string
+    #[error("Invalid hex encoded string `{0}`")]
+    InvalidHexString(String),
+    /// The string is not a valid Base58 encoded string. Contains the decode error
+    #[error("Invalid Base58 encoded string. Error: `{0}`")]
+    Base58Decode(String),
+    /// The string is not a valid Base64 encoded string. Contains the decode error
+    #[error("Invalid Base64 encoded string. Error: `{0}`")]
+    Base64Decode(String),
     /// The nonce is required to be at least 8 characters long
     #[error("The nonce is required to be at least 8 characters long")]
     NonceMustBeAtLeast8Characters,
@@ -155,58 +244,339 @@ pub enum WalletError {
     /// The bytes provided for the Ed25519 Public Key are invalid
     #[error("The bytes provided for the Ed25519 Public Key are invalid")]
     InvalidEd25519PublicKeyBytes,
+    /// An Ed25519 key parsing or signature verification error converted from an
+    /// [ed25519_dalek::SignatureError] using `?`, with the underlying error as its
+    /// [source](std::error::Error::source). ed25519-dalek is built without its `std`
+    /// feature so the message does not say which check failed.
+    /// [crate::Utils::public_key] and [crate::Utils::verify_signature] still return
+    /// [WalletError::InvalidEd25519PublicKeyBytes] and [WalletError::InvalidSignature]
+    #[error("Ed25519 error: `{0}`")]
+    Ed25519(#[source] Ed25519Error),
     /// The function call to Sign A Message Is Missing
     #[error("The function call to Sign A Message Is Missing")]
     MissingSignMessageFunction,
     /// The message sent to the wallet to be signed is different from the message the wallet responded with
     #[error("The message sent to the wallet to be signed is different from the message the wallet responded with")]
     SignedMessageMismatch,
+    /// The browser wallet signed the message with a different account than the one requested
+    #[error("The message was signed by `{signer}` instead of the requested account `{expected}`")]
+    UnexpectedSigner {
+        /// The Base58 address of the requested account
+        expected: String,
+        /// The Base58 address of the account that signed the message
+        signer: String,
+    },
     /// The Wallet returned an empty array of  signed messages
     #[error("The Wallet returned an empty array of  signed messages")]
     ReceivedAnEmptySignedMessagesArray,
     /// The `solana:signTransaction` function is missing in the provided wallet
     #[error("The `solana:signTransaction` function is missing in the provided wallet")]
     MissingSignTransactionFunction,
+    /// The `solana:signAndSendTransaction` function is missing in the provided wallet
+    #[error("The `solana:signAndSendTransaction` function is missing in the provided wallet")]
+    MissingSignAndSendTransactionFunction,
+    /// A seed used to derive a program address is longer than 32 bytes or more than 16 seeds were used
+    #[error(
+        "A program address can only be derived from at most 16 seeds of at most 32 bytes each"
+    )]
+    InvalidProgramAddressSeeds,
+    /// None of the bump seeds derived an address that is off the Ed25519 curve
+    #[error("Unable to find a viable program address bump seed")]
+    ProgramAddressNotFound,
+    /// The transaction bytes could not be decoded as a well-formed Solana transaction
+    #[error("Malformed transaction: {0}")]
+    MalformedTransaction(String),
     /// The `sendAndSignTransaction` method did not return any signature
     #[error("The `sendAndSignTransaction` method did not return any signature")]
     SendAndSignTransactionSignatureEmpty,
+    /// The browser wallet did not return a signed transaction
+    #[error("The browser wallet did not return a signed transaction")]
+    SignedTransactionEmpty,
+    /// The browser wallet returned a different number of signed transactions
+    /// than the number of transactions it was requested to sign
+    #[error(
+        "Expected `{expected}` signed transactions but the browser wallet returned `{received}`"
+    )]
+    SignedTransactionsCountMismatch {
+        /// The number of transactions sent to the browser wallet
+        expected: usize,
+        /// The number of signed transactions returned by the browser wallet
+        received: usize,
+    },
+    /// The payload of an off-chain message exceeds the maximum length of its format
+    #[error("The off-chain message payload of `{length}` bytes exceeds the maximum length of `{max}` bytes of its format")]
+    OffchainMessageTooLong {
+        /// The length of the payload in bytes
+        length: usize,
+        /// The maximum length in bytes of a payload in the format
+        max: usize,
+    },
+    /// The payload of an off-chain message is empty or contains characters not allowed by its format
+    #[error("Invalid off-chain message: {0}")]
+    InvalidOffchainMessage(String),
+    /// The user rejected the request in the browser wallet, for example by clicking `Reject`
+    /// in the approval popup. Wallets reject the request with the code `4001`
+    #[error("The user rejected the request")]
+    UserRejected,
+    /// The connect request was not made in response to a user gesture like a click
+    /// so the browser may block the wallet popup before the user sees it.
+    /// Unlike [WalletError::UserRejected] the user never declined the request.
+    /// See `WalletAdapter::require_user_gesture`
+    #[error("A connect request must be made in response to a user gesture like a click")]
+    UserGestureRequired,
+    /// The browser wallet has not authorized the requested account or method,
+    /// wallets reject the request with the code `4100`
+    #[error("The request is not authorized by the browser wallet: {0}")]
+    Unauthorized(String),
     /// An operation resulted in an error. This is a convenience error that you can use to return any error
     /// that was not caused by the wallet adapter, example, parsing a recipient address or the result of parsing
-    /// the body of a HTTP response using serde resulted in an error. Remember, this error is not from the [crate::WalletAdapter]
+    /// the body of a HTTP response using serde resulted in an error. Remember, this error is not from the `WalletAdapter`
     /// but instead an external error.
     #[error("An operation resulted in an error `{0}`.")]
     Op(String),
 }
 
+/// The code wallets reject a request with when the user rejects it
+pub const USER_REJECTED_ERROR_CODE: i32 = 4001;
+
+/// The code wallets reject a request with when the account or method is not authorized
+pub const UNAUTHORIZED_ERROR_CODE: i32 = 4100;
+
+/// The code wallets reject a request with when the wallet encountered an internal error
+pub const INTERNAL_ERROR_CODE: i32 = -32603;
+
+#[cfg(feature = "browser")]
+impl WalletError {
+    /// Convert the value a wallet rejected a promise with into a [WalletError].
+    /// The `code` and `message` of the value are reflected and the well-known codes
+    /// [USER_REJECTED_ERROR_CODE], [UNAUTHORIZED_ERROR_CODE] and [INTERNAL_ERROR_CODE]
+    /// are mapped to [WalletError::UserRejected], [WalletError::Unauthorized]
+    /// and [WalletError::InternalError]. Some wallets reject the user rejecting the request
+    /// with only a message. A JavaScript `Error` is converted to [WalletError::JsError]
+    /// and any other value falls back to [WalletError::InternalError] with the message
+    pub fn from_js_error(value: &JsValue) -> WalletError {
+        let reflect = |key: &str| Reflect::get(value, &key.into()).ok();
+
+        let message = value
+            .as_string()
+            .or_else(|| reflect("message").and_then(|message| message.as_string()));
+        let code = reflect("code")
+            .and_then(|code| code.as_f64())
+            .map(|code| code as i32);
+
+        let message_or_value = || message.clone().unwrap_or_else(|| format!("{value:?}"));
+
+        match code {
+            Some(USER_REJECTED_ERROR_CODE) => return Self::UserRejected,
+            Some(UNAUTHORIZED_ERROR_CODE) => return Self::Unauthorized(message_or_value()),
+            Some(INTERNAL_ERROR_CODE) => return Self::InternalError(message_or_value()),
+            _ => (),
+        }
+
+        let is_user_rejection = message
+            .as_ref()
+            .map(|message| message.to_lowercase())
+            .is_some_and(|message| {
+                message.contains("user rejected") || message.contains("rejected the request")
+            });
+        if is_user_rejection {
+            return Self::UserRejected;
+        }
+
+        if value.is_instance_of::<js_sys::Error>() {
+            let reflect_string = |key: &str| {
+                reflect(key)
+                    .and_then(|inner| inner.as_string())
+                    .unwrap_or_default()
+            };
+
+            return Self::JsError {
+                name: reflect_string("name"),
+                message: reflect_string("message"),
+                stack: reflect_string("stack"),
+            };
+        }
+
+        Self::InternalError(message_or_value())
+    }
+
+    /// Convert the error a connect request is rejected with into
+    /// [WalletError::WalletConnectError] keeping [WalletError::UserRejected]
+    /// and [WalletError::Unauthorized]
+    pub(crate) fn connect_error(value: JsValue) -> Self {
+        match Self::from_js_error(&value) {
+            error @ (WalletError::UserRejected | WalletError::Unauthorized(_)) => error,
+            error => WalletError::WalletConnectError(error.to_string()),
+        }
+    }
+
+    /// Convert the error a silent connect request is rejected with into
+    /// [WalletError::SilentConnectUnsupported] if it is a `TypeError`, which is
+    /// how wallets reject an input they do not understand, otherwise see [Self::connect_error]
+    pub(crate) fn silent_connect_error(value: JsValue) -> Self {
+        if value.is_instance_of::<js_sys::TypeError>() {
+            WalletError::SilentConnectUnsupported(Self::from_js_error(&value).to_string())
+        } else {
+            Self::connect_error(value)
+        }
+    }
+}
+
+impl WalletError {
+    /// Check whether the error is a transient failure of the browser wallet or the RPC node
+    /// it sends transactions to, like a blockhash the node has not seen yet, a node that
+    /// is behind or a network failure, which may succeed when the request is retried.
+    /// Errors like [WalletError::UserRejected] or a missing feature are never transient.
+    /// An expired blockhash (`block height exceeded`) is not transient either since
+    /// the transaction has to be rebuilt with a new blockhash before it can succeed
+    pub fn is_transient(&self) -> bool {
+        const TRANSIENT_MESSAGES: [&str; 8] = [
+            "blockhash not found",
+            "node is behind",
+            "node is unhealthy",
+            "timed out",
+            "timeout",
+            "too many requests",
+            "failed to fetch",
+            "network error",
+        ];
+
+        let message = match self {
+            Self::Timeout(_) => return true,
+            Self::JsError { message, .. } => message,
+            Self::InternalError(message) => message,
+            _ => return false,
+        }
+        .to_lowercase();
+
+        TRANSIENT_MESSAGES
+            .iter()
+            .any(|transient| message.contains(transient))
+    }
+}
+
+#[cfg(feature = "browser")]
 impl From<JsValue> for WalletError {
     fn from(value: JsValue) -> Self {
-        let reflect = |key: &str| -> Result<String, Self> {
-            Reflect::get(&value, &key.into())
-                .map_err(|error: JsValue| WalletError::InternalError(format!("{:?}", &error)))?
-                .as_string()
-                .ok_or(WalletError::InternalError(format!(
-                    "Reflecting `{key}` in `{value:?}` did not yield a JsString"
-                )))
-        };
+        Self::from_js_error(&value)
+    }
+}
 
-        let name = match reflect("name") {
-            Ok(inner) => inner,
-            Err(error) => return error,
-        };
+#[cfg(test)]
+mod errors_tests {
+    use super::*;
 
-        let stack = match reflect("stack") {
-            Ok(inner) => inner,
-            Err(error) => return error,
-        };
-        let message = match reflect("message") {
-            Ok(inner) => inner,
-            Err(error) => return error,
+    #[test]
+    fn ed25519_error_source() {
+        use ed25519_dalek::{Signer, SigningKey, Verifier};
+        use std::error::Error;
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let signature = signing_key.sign(b"ed25519");
+        let error = WalletError::from(
+            signing_key
+                .verifying_key()
+                .verify(b"other", &signature)
+                .unwrap_err(),
+        );
+
+        let WalletError::Ed25519(source) = &error else {
+            panic!("Expected `WalletError::Ed25519`, found `{error:?}`");
         };
+        assert!(!source.message().is_empty());
+        assert_eq!(
+            error.source().map(|error| error.to_string()),
+            Some(source.message().to_string())
+        );
+    }
 
-        Self::JsError {
-            message,
-            name,
-            stack,
+    #[test]
+    fn is_transient() {
+        assert!(WalletError::InternalError("Blockhash not found".to_string()).is_transient());
+        assert!(WalletError::Timeout(1000).is_transient());
+        assert!(WalletError::JsError {
+            name: "Error".to_string(),
+            message: "Node is behind by 42 slots".to_string(),
+            stack: String::new(),
         }
+        .is_transient());
+
+        assert!(!WalletError::UserRejected.is_transient());
+        assert!(!WalletError::InternalError(
+            "Signature has expired: block height exceeded".to_string()
+        )
+        .is_transient());
+        assert!(!WalletError::UserGestureRequired.is_transient());
+        assert!(!WalletError::from(ed25519_dalek::SignatureError::new()).is_transient());
+        assert!(!WalletError::from(bs58::decode::Error::InvalidCharacter {
+            character: 'l',
+            index: 3
+        })
+        .is_transient());
+        assert!(!WalletError::MissingSignAndSendTransactionFunction.is_transient());
+        assert!(!WalletError::InternalError("Invalid transaction".to_string()).is_transient());
+    }
+
+    #[cfg(all(feature = "browser", target_arch = "wasm32"))]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn from_js_error() {
+        use crate::Reflection;
+
+        let mut rejection = Reflection::new_object();
+        rejection
+            .set_object(&"code".into(), &JsValue::from_f64(4001f64))
+            .unwrap();
+        assert_eq!(
+            WalletError::from(rejection.take()),
+            WalletError::UserRejected
+        );
+
+        let mut rejection = Reflection::new_object();
+        rejection
+            .set_object_str("message", "User rejected the request.")
+            .unwrap();
+        assert_eq!(
+            WalletError::connect_error(rejection.take()),
+            WalletError::UserRejected
+        );
+
+        let error = web_sys::js_sys::Error::new("Wallet locked");
+        assert!(matches!(
+            WalletError::connect_error(error.clone().into()),
+            WalletError::WalletConnectError(_)
+        ));
+        assert!(matches!(
+            WalletError::silent_connect_error(error.into()),
+            WalletError::WalletConnectError(_)
+        ));
+        let error = web_sys::js_sys::TypeError::new("Unknown input `silent`");
+        assert!(matches!(
+            WalletError::silent_connect_error(error.into()),
+            WalletError::SilentConnectUnsupported(_)
+        ));
+
+        let mut unauthorized = Reflection::new_object();
+        unauthorized
+            .set_object(&"code".into(), &JsValue::from_f64(4100f64))
+            .unwrap();
+        unauthorized
+            .set_object_str("message", "Account not connected")
+            .unwrap();
+        assert_eq!(
+            WalletError::from_js_error(&unauthorized.take()),
+            WalletError::Unauthorized("Account not connected".to_string())
+        );
+
+        let mut internal = Reflection::new_object();
+        internal
+            .set_object(&"code".into(), &JsValue::from_f64(-32603f64))
+            .unwrap();
+        internal
+            .set_object_str("message", "Node is behind")
+            .unwrap();
+        assert_eq!(
+            WalletError::from_js_error(&internal.take()),
+            WalletError::InternalError("Node is behind".to_string())
+        );
     }
 }
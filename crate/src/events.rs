@@ -1,6 +1,14 @@
-use std::rc::Rc;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    pin::Pin,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+};
 
-use async_channel::{Receiver, Sender};
+use async_channel::{bounded, Receiver, Sender, TrySendError};
+use futures_core::Stream;
 use web_sys::{
     js_sys::{Object, Reflect},
     wasm_bindgen::{prelude::Closure, JsValue},
@@ -8,8 +16,8 @@ use web_sys::{
 };
 
 use crate::{
-    Reflection, StorageType, Utils, Wallet, WalletAccount, WalletAdapter, WalletError,
-    WalletResult, WINDOW_APP_READY_EVENT_TYPE,
+    send_wallet_event, Reflection, Utils, Wallet, WalletAccount, WalletAdapter, WalletError,
+    WalletResult, WalletStorage, WINDOW_APP_READY_EVENT_TYPE,
 };
 
 /// The `Sender` part of an [async_channel::bounded] channel
@@ -18,6 +26,207 @@ pub type WalletEventSender = Sender<WalletEvent>;
 /// The `Receiver` part of an [async_channel::bounded] channel
 pub type WalletEventReceiver = Receiver<WalletEvent>;
 
+/// A [Stream] of the [WalletEvent]s received by a [WalletEventReceiver]
+/// for consuming the events using stream combinators, for example those of `StreamExt`.
+/// The stream ends once the channel is closed and all its events have been received
+#[derive(Debug)]
+pub struct WalletEventStream(Pin<Box<WalletEventReceiver>>);
+
+impl WalletEventStream {
+    /// Wrap the `receiver`
+    pub fn new(receiver: WalletEventReceiver) -> Self {
+        Self(Box::pin(receiver))
+    }
+
+    /// Close the channel so no more events are sent and the stream ends
+    /// after the pending events are received, for a graceful shutdown.
+    /// Returns `false` if the channel was already closed
+    pub fn close(&self) -> bool {
+        self.0.close()
+    }
+
+    /// Get a clone of the [WalletEventReceiver]
+    pub fn receiver(&self) -> WalletEventReceiver {
+        self.0.as_ref().get_ref().clone()
+    }
+}
+
+impl Clone for WalletEventStream {
+    fn clone(&self) -> Self {
+        Self::new(self.receiver())
+    }
+}
+
+impl From<WalletEventReceiver> for WalletEventStream {
+    fn from(value: WalletEventReceiver) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Stream for WalletEventStream {
+    type Item = WalletEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(context)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// A predicate that decides whether a [WalletEvent] is delivered to the [WalletEventReceiver].
+/// Events for which the predicate returns `false` are dropped.
+pub type WalletEventFilter = Box<dyn Fn(&WalletEvent) -> bool>;
+
+/// A callback registered using [WalletAdapter::on] that is invoked for every [WalletEvent]
+pub type WalletEventHandler = Box<dyn Fn(&WalletEvent)>;
+
+/// Holds the optional [WalletEventFilter] shared between the [WalletAdapter]
+/// and the background task that delivers events to the [WalletEventReceiver]
+#[derive(Clone, Default)]
+pub(crate) struct EventFilter(Rc<RefCell<Option<WalletEventFilter>>>);
+
+impl EventFilter {
+    /// Replace the predicate, `None` delivers all events
+    pub(crate) fn set(&self, predicate: Option<WalletEventFilter>) {
+        *self.0.borrow_mut() = predicate;
+    }
+
+    /// Check whether the `event` should be delivered
+    pub(crate) fn allows(&self, event: &WalletEvent) -> bool {
+        self.0
+            .borrow()
+            .as_ref()
+            .is_none_or(|predicate| predicate(event))
+    }
+
+    /// Invoke the `handlers` for each event received from `receiver` and then
+    /// deliver the event to `sender` if the predicate allows it.
+    /// Delivered events are also recorded in the `replay` buffer.
+    /// Runs until the `receiver` or `sender` is closed
+    pub(crate) async fn forward(
+        &self,
+        receiver: WalletEventReceiver,
+        sender: WalletEventSender,
+        replay: EventReplay,
+        handlers: EventHandlers,
+    ) {
+        while let Ok(event) = receiver.recv().await {
+            handlers.invoke(&event);
+
+            if self.allows(&event) {
+                replay.record(&event);
+
+                if sender.send(event).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for EventFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let filter = if self.0.borrow().is_some() {
+            "Some(WalletEventFilter)"
+        } else {
+            "None"
+        };
+
+        f.debug_tuple("EventFilter").field(&filter).finish()
+    }
+}
+
+/// The callbacks registered using [WalletAdapter::on]
+#[derive(Clone, Default)]
+pub(crate) struct EventHandlers(Rc<RefCell<Vec<WalletEventHandler>>>);
+
+impl EventHandlers {
+    /// Register a `handler` invoked for every event
+    pub(crate) fn push(&self, handler: WalletEventHandler) {
+        self.0.borrow_mut().push(handler);
+    }
+
+    /// Invoke all the handlers in the order they were registered
+    pub(crate) fn invoke(&self, event: &WalletEvent) {
+        self.0.borrow().iter().for_each(|handler| handler(event));
+    }
+}
+
+impl core::fmt::Debug for EventHandlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventHandlers")
+            .field(&self.0.borrow().len())
+            .finish()
+    }
+}
+
+/// The maximum number of recent events kept for subscribers
+/// created using [WalletAdapter::subscribe_with_replay]
+pub const EVENT_REPLAY_CAPACITY: usize = 16;
+
+/// A ring buffer of the most recent events delivered to the [WalletEventReceiver]
+/// together with the subscribers that receive a copy of each delivered event
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventReplay(Rc<RefCell<EventReplayState>>);
+
+#[derive(Debug, Default)]
+struct EventReplayState {
+    events: VecDeque<WalletEvent>,
+    subscribers: Vec<WalletEventSender>,
+}
+
+impl EventReplay {
+    /// Record a delivered `event` and send it to the subscribers.
+    /// Subscribers whose receiver has been dropped are removed
+    pub(crate) fn record(&self, event: &WalletEvent) {
+        if event == &WalletEvent::Skip {
+            return;
+        }
+
+        let mut state = self.0.borrow_mut();
+
+        if state.events.len() == EVENT_REPLAY_CAPACITY {
+            state.events.pop_front();
+        }
+        state.events.push_back(event.clone());
+
+        state.subscribers.retain(|subscriber| {
+            !matches!(
+                subscriber.try_send(event.clone()),
+                Err(TrySendError::Closed(_))
+            )
+        });
+    }
+
+    /// Create a subscriber that first receives the last `replay` recorded events.
+    /// The channel of the subscriber holds at least `capacity` events
+    pub(crate) fn subscribe(&self, replay: usize, capacity: usize) -> WalletEventReceiver {
+        let mut state = self.0.borrow_mut();
+
+        let replay = replay.min(state.events.len());
+        let (sender, receiver) = bounded::<WalletEvent>(capacity.max(replay).max(1));
+
+        state
+            .events
+            .iter()
+            .skip(state.events.len() - replay)
+            .for_each(|event| {
+                // The channel has enough capacity for all the replayed events
+                let _ = sender.try_send(event.clone());
+            });
+
+        state.subscribers.push(sender);
+
+        receiver
+    }
+}
+
+/// Set once the `Register` and `AppReady` events have been initialized
+/// for the page, see [InitEvents::init]
+static EVENTS_INITIALIZED: AtomicBool = AtomicBool::new(false);
+
 /// Used to initialize the `Register` and `AppReady` events to the browser window
 #[derive(Debug, PartialEq, Eq)]
 pub struct InitEvents<'a> {
@@ -31,43 +240,67 @@ impl<'a> InitEvents<'a> {
     }
 
     /// Register events by providing a [crate::WalletStorage] that is used to store
-    /// all registered wallets
+    /// all registered wallets.
+    /// The events are only initialized once per page. Calling this again, for example
+    /// when a framework mounts a component twice and initializes a second [WalletAdapter],
+    /// is a no-op so the `register-wallet` listeners do not stack up and the `AppReady`
+    /// event is not dispatched twice. Only the adapter initialized first receives
+    /// the registered wallets so share that adapter, whose clones share its storage,
+    /// or use [Self::force_reinit] to register the events and dispatch the `AppReady`
+    /// event again for another adapter
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn init(&self, adapter: &mut WalletAdapter) -> WalletResult<()> {
+        if EVENTS_INITIALIZED.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        self.force_reinit(adapter)
+    }
+
+    /// Same as [Self::init] but always registers the events and dispatches
+    /// the `AppReady` event even if they were initialized before for the page
+    pub fn force_reinit(&self, adapter: &mut WalletAdapter) -> WalletResult<()> {
         let storage = adapter.storage();
-        self.register_wallet_event(storage.clone_inner())?;
-        self.dispatch_app_event(storage.clone_inner())?;
+        let sender = adapter.wallet_events_sender();
+        self.register_wallet_event(storage.clone(), sender.clone())?;
+        self.dispatch_app_event(storage.clone(), sender)?;
+        EVENTS_INITIALIZED.store(true, Ordering::SeqCst);
 
         Ok(())
     }
 
     /// An App Ready event registered to the browser window
-    pub fn dispatch_app_event(&self, storage: StorageType) -> WalletResult<()> {
+    pub fn dispatch_app_event(
+        &self,
+        storage: WalletStorage,
+        sender: WalletEventSender,
+    ) -> WalletResult<()> {
         let app_ready_init = CustomEventInit::new();
         app_ready_init.set_bubbles(false);
         app_ready_init.set_cancelable(false);
         app_ready_init.set_composed(false);
-        app_ready_init.set_detail(&Self::register_object(storage));
+        app_ready_init.set_detail(&Self::register_object(storage, sender));
 
-        let app_ready_ev = CustomEvent::new_with_event_init_dict(
-            WINDOW_APP_READY_EVENT_TYPE,
-            &app_ready_init,
-        )
-        .map_err(|e| WalletError::InternalError(format!(
-            "Failed to create app ready event: {:?}",
-            e
-        )))?;
+        let app_ready_ev =
+            CustomEvent::new_with_event_init_dict(WINDOW_APP_READY_EVENT_TYPE, &app_ready_init)
+                .map_err(|e| {
+                    WalletError::InternalError(format!("Failed to create app ready event: {:?}", e))
+                })?;
 
-        self.window
-            .dispatch_event(&app_ready_ev)
-            .map_err(|e| WalletError::InternalError(format!(
-                "Failed to dispatch app ready event: {:?}",
-                e
-            )))?;
+        self.window.dispatch_event(&app_ready_ev).map_err(|e| {
+            WalletError::InternalError(format!("Failed to dispatch app ready event: {:?}", e))
+        })?;
+
+        Ok(())
     }
 
     /// The register wallet event registered to the browser window
-    pub fn register_wallet_event(&self, storage: StorageType) -> WalletResult<()> {
-        let inner_storage = Rc::clone(&storage);
+    pub fn register_wallet_event(
+        &self,
+        storage: WalletStorage,
+        sender: WalletEventSender,
+    ) -> WalletResult<()> {
+        let inner_storage = storage.clone();
 
         let listener_closure = Closure::wrap(Box::new(move |custom_event: CustomEvent| {
             let detail = Reflection::new(custom_event
@@ -76,7 +309,7 @@ impl<'a> InitEvents<'a> {
 
             Utils::jsvalue_to_error(detail.call1(
                 &JsValue::null(),
-                &Self::register_object(inner_storage.clone()),
+                &Self::register_object(inner_storage.clone(), sender.clone()),
             ))
             .unwrap()
         }) as Box<dyn Fn(_)>);
@@ -94,29 +327,92 @@ impl<'a> InitEvents<'a> {
         Ok(())
     }
 
-    /// Sets the object to be passed to the register function
-    pub fn register_object(storage: StorageType) -> Object {
+    /// Wallets are keyed by the hash of their name, see [WalletStorage::key].
+    /// Some extensions dispatch the register event multiple times so an existing entry
+    /// is only replaced if the new entry advertises more features.
+    fn insert_wallet(storage: &WalletStorage, wallet: Wallet) -> RegisterOutcome {
+        let key = storage.key(wallet.name());
+        let storage = storage.clone_inner();
+        let mut storage = storage.borrow_mut();
+
+        let outcome = match storage.get(&key) {
+            None => RegisterOutcome::Inserted,
+            Some(existing) if existing.features_count() < wallet.features_count() => {
+                let conflict = (existing.name() != wallet.name()
+                    || existing.version() != wallet.version())
+                .then(|| existing.name().to_string());
+
+                RegisterOutcome::Replaced { conflict }
+            }
+            Some(_) => return RegisterOutcome::Ignored,
+        };
+
+        storage.insert(key, wallet);
+
+        outcome
+    }
+
+    /// Sets the object to be passed to the register function.
+    /// A [WalletEvent::WalletRegistered] event is sent through the `sender`
+    /// each time a wallet is successfully registered. If the wallet replaces a different
+    /// build of a wallet with the same name a [WalletEvent::BackgroundTaskError]
+    /// is sent before it. A wallet that cannot be parsed is not registered and the error
+    /// is sent as a [WalletEvent::BackgroundTaskError], except for errors caused
+    /// by chains or features not supported by the adapter which are skipped.
+    pub fn register_object(storage: WalletStorage, sender: WalletEventSender) -> Object {
+        let register_sender = sender.clone();
         // The `register` function that logs and returns a closure like in your JS code
         let register =
             Closure::wrap(
                 Box::new(move |value: JsValue| match Wallet::from_jsvalue(value) {
                     Ok(wallet) => {
-                        let inner_outcome = storage.clone();
+                        let wallet_name = wallet.name().to_string();
+
+                        #[cfg(feature = "tracing")]
+                        let _span =
+                            tracing::info_span!("register_wallet", wallet = %wallet_name).entered();
+
+                        let outcome = Self::insert_wallet(&storage, wallet);
+
+                        #[cfg(feature = "tracing")]
+                        tracing::info!(?outcome);
 
-                        inner_outcome.borrow_mut().insert(
-                            blake3::hash(wallet.name().to_lowercase().as_bytes()),
-                            wallet,
-                        );
+                        let events = match outcome {
+                            RegisterOutcome::Inserted
+                            | RegisterOutcome::Replaced { conflict: None } => {
+                                vec![WalletEvent::WalletRegistered(wallet_name)]
+                            }
+                            RegisterOutcome::Replaced {
+                                conflict: Some(previous_name),
+                            } => vec![
+                                WalletEvent::BackgroundTaskError(
+                                    WalletError::WalletRegistrationConflict {
+                                        previous: previous_name,
+                                        replacement: wallet_name.clone(),
+                                    },
+                                ),
+                                WalletEvent::WalletRegistered(wallet_name),
+                            ],
+                            RegisterOutcome::Ignored => Vec::new(),
+                        };
+
+                        let sender = sender.clone();
+                        wasm_bindgen_futures::spawn_local(async move {
+                            for event in events {
+                                send_wallet_event(event, sender.clone()).await
+                            }
+                        });
                     }
+                    // Wallets with chains or features not supported by the adapter are skipped
+                    Err(error) if error.to_string().contains("is not supported") => {}
                     Err(error) => {
-                        let error = error.to_string();
-                        if error.contains("is not supported") {
-                        } else {
-                            web_sys::console::error_2(
-                                &"REGISTER EVENT ERROR".into(),
-                                &error.into(),
-                            );
-                        }
+                        #[cfg(feature = "tracing")]
+                        tracing::error!(%error, "register event error");
+
+                        let sender = sender.clone();
+                        wasm_bindgen_futures::spawn_local(async move {
+                            send_wallet_event(WalletEvent::BackgroundTaskError(error), sender).await
+                        });
                     }
                 }) as Box<dyn Fn(_)>,
             );
@@ -129,13 +425,35 @@ impl<'a> InitEvents<'a> {
             &JsValue::from("register"),
             &register.into_js_value(),
         ) {
-            web_sys::console::error_2(&"REGISTER EVENT ERROR".into(), &error);
+            wasm_bindgen_futures::spawn_local(async move {
+                send_wallet_event(
+                    WalletEvent::BackgroundTaskError(error.into()),
+                    register_sender,
+                )
+                .await
+            });
         }
 
         register_object
     }
 }
 
+/// The outcome of inserting a wallet into storage
+/// when it dispatches the register event
+#[derive(Debug, PartialEq, Eq)]
+enum RegisterOutcome {
+    /// No wallet with the same name had been registered
+    Inserted,
+    /// A wallet with the same name had been registered and was replaced
+    /// since the new entry advertises more features. The `conflict` contains the name
+    /// of the previous entry if it was a different build of the wallet
+    /// (different casing of the name or a different version)
+    Replaced { conflict: Option<String> },
+    /// A wallet with the same name, advertising the same or more features,
+    /// had already been registered so the new entry was discarded
+    Ignored,
+}
+
 /// Events emitted by connected browser extensions
 /// when an account is connected, disconnected or changed.
 /// Wallets implementing the wallet standard emit these events
@@ -157,6 +475,15 @@ pub enum WalletEvent {
     /// `on` method from the `[standard:events]` namespace
     /// (when an account is connected, changed or disconnected)
     BackgroundTaskError(WalletError),
+    /// Reconnecting to a wallet using [crate::WalletAdapter::reconnect] failed,
+    /// for example because the account is no longer authorized by the wallet.
+    /// This is expected when the user revoked access, so an app can quietly show
+    /// the connect button again instead of reporting an error
+    ReconnectFailed(WalletError),
+    /// A wallet has been registered and added to the [crate::WalletStorage].
+    /// This contains the name of the registered wallet and is useful
+    /// for updating a list of wallets when a browser extension registers late.
+    WalletRegistered(String),
     /// An event was emitted by a wallet that is not connected.
     #[default]
     Skip,
@@ -170,8 +497,235 @@ impl core::fmt::Display for WalletEvent {
             Self::Disconnected => "Disconnected",
             Self::AccountChanged(_) => "Account Changed",
             Self::BackgroundTaskError(error) => &format!("Task error: {error:?}"),
+            Self::ReconnectFailed(error) => &format!("Reconnect failed: {error}"),
+            Self::WalletRegistered(wallet_name) => &format!("Wallet Registered: {wallet_name}"),
             Self::Skip => "Skipped",
         };
         write!(f, "{}", as_str)
     }
 }
+
+/// The events pulled from a [WalletEventReceiver] by [drain_latest]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct DrainedEvents {
+    latest: Option<WalletEvent>,
+    errors: Vec<WalletError>,
+    registered_wallets: Vec<String>,
+}
+
+impl DrainedEvents {
+    /// The most recent connection state event, one of [WalletEvent::Connected],
+    /// [WalletEvent::Reconnected], [WalletEvent::AccountChanged], [WalletEvent::Disconnected]
+    /// or [WalletEvent::ReconnectFailed]
+    pub fn latest(&self) -> Option<&WalletEvent> {
+        self.latest.as_ref()
+    }
+
+    /// The errors of all the [WalletEvent::BackgroundTaskError] events in the order received
+    pub fn errors(&self) -> &[WalletError] {
+        &self.errors
+    }
+
+    /// The names of all the wallets from [WalletEvent::WalletRegistered] events in the order received
+    pub fn registered_wallets(&self) -> &[String] {
+        &self.registered_wallets
+    }
+}
+
+/// Pull all the pending events from the `receiver` without waiting for new events.
+/// This is useful in a render loop where only the latest connection state matters,
+/// for example when many [WalletEvent::AccountChanged] events arrive in quick succession
+/// only the most recent one is returned. Errors and registered wallets are collected
+/// instead of being dropped and [WalletEvent::Skip] events are ignored.
+pub fn drain_latest(receiver: &WalletEventReceiver) -> DrainedEvents {
+    let mut drained = DrainedEvents::default();
+
+    while let Ok(event) = receiver.try_recv() {
+        match event {
+            WalletEvent::Connected(_)
+            | WalletEvent::Reconnected(_)
+            | WalletEvent::AccountChanged(_)
+            | WalletEvent::Disconnected
+            | WalletEvent::ReconnectFailed(_) => {
+                drained.latest.replace(event);
+            }
+            WalletEvent::BackgroundTaskError(error) => drained.errors.push(error),
+            WalletEvent::WalletRegistered(wallet_name) => {
+                drained.registered_wallets.push(wallet_name)
+            }
+            WalletEvent::Skip => (),
+        }
+    }
+
+    drained
+}
+
+#[cfg(test)]
+pub(crate) mod event_filter_tests {
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::Arc,
+        task::{Wake, Waker},
+    };
+
+    use super::*;
+
+    struct NoopWake;
+
+    impl Wake for NoopWake {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// A [Waker] that does nothing, since `Waker::noop` requires Rust 1.85
+    /// which is newer than the `rust-version` of the crate
+    pub(crate) fn noop_waker() -> Waker {
+        Waker::from(Arc::new(NoopWake))
+    }
+
+    /// Poll the `future` until it is ready without an executor
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn filter_drops_events() {
+        let filter = EventFilter::default();
+        filter.set(Some(Box::new(|event| {
+            !matches!(event, WalletEvent::WalletRegistered(_))
+        })));
+
+        let (internal_sender, internal_receiver) = bounded::<WalletEvent>(5);
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+
+        internal_sender
+            .try_send(WalletEvent::WalletRegistered("Foo".to_string()))
+            .unwrap();
+        internal_sender.try_send(WalletEvent::Disconnected).unwrap();
+        internal_sender.close();
+
+        let handled = Rc::new(RefCell::new(Vec::<WalletEvent>::new()));
+        let handlers = EventHandlers::default();
+        let handled_events = handled.clone();
+        handlers.push(Box::new(move |event| {
+            handled_events.borrow_mut().push(event.clone())
+        }));
+
+        block_on(filter.forward(internal_receiver, sender, EventReplay::default(), handlers));
+
+        assert_eq!(receiver.try_recv(), Ok(WalletEvent::Disconnected));
+        assert!(receiver.try_recv().is_err());
+
+        // Handlers are invoked for the events dropped by the filter
+        assert_eq!(
+            handled.borrow().as_slice(),
+            &[
+                WalletEvent::WalletRegistered("Foo".to_string()),
+                WalletEvent::Disconnected
+            ]
+        );
+
+        filter.set(None);
+        assert!(filter.allows(&WalletEvent::WalletRegistered("Foo".to_string())));
+    }
+
+    #[test]
+    fn drain_latest_keeps_errors() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+        assert_eq!(drain_latest(&receiver), DrainedEvents::default());
+
+        sender.try_send(WalletEvent::Disconnected).unwrap();
+        sender
+            .try_send(WalletEvent::BackgroundTaskError(WalletError::Timeout(1000)))
+            .unwrap();
+        sender
+            .try_send(WalletEvent::WalletRegistered("Foo".to_string()))
+            .unwrap();
+        sender.try_send(WalletEvent::Disconnected).unwrap();
+        sender.try_send(WalletEvent::Skip).unwrap();
+
+        let drained = drain_latest(&receiver);
+        assert_eq!(drained.latest(), Some(&WalletEvent::Disconnected));
+        assert_eq!(drained.errors(), &[WalletError::Timeout(1000)]);
+        assert_eq!(drained.registered_wallets(), &["Foo".to_string()]);
+        assert!(receiver.is_empty());
+    }
+
+    #[test]
+    fn event_stream() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+        let mut stream = WalletEventStream::new(receiver);
+        let next = |stream: &mut WalletEventStream| {
+            block_on(std::future::poll_fn(|context| {
+                Pin::new(&mut *stream).poll_next(context)
+            }))
+        };
+
+        sender.try_send(WalletEvent::Disconnected).unwrap();
+        assert!(stream.close());
+
+        assert_eq!(next(&mut stream), Some(WalletEvent::Disconnected));
+        assert_eq!(next(&mut stream), None);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod register_wallet_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn case_sensitive_names() {
+        let storage = WalletStorage::new(false);
+        assert_eq!(
+            InitEvents::insert_wallet(&storage, Wallet::with_name("Solflare")),
+            RegisterOutcome::Inserted
+        );
+        assert_eq!(
+            InitEvents::insert_wallet(&storage, Wallet::with_name("solflare")),
+            RegisterOutcome::Ignored
+        );
+        assert_eq!(storage.len(), 1);
+        assert!(storage.get_wallet("SOLFLARE").is_some());
+
+        let storage = WalletStorage::new(true);
+        assert_eq!(
+            InitEvents::insert_wallet(&storage, Wallet::with_name("Solflare")),
+            RegisterOutcome::Inserted
+        );
+        assert_eq!(
+            InitEvents::insert_wallet(&storage, Wallet::with_name("solflare")),
+            RegisterOutcome::Inserted
+        );
+        assert_eq!(storage.len(), 2);
+        assert!(storage.get_wallet("SOLFLARE").is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn init_once_per_page() {
+        WalletAdapter::init().unwrap();
+        assert!(EVENTS_INITIALIZED.load(Ordering::SeqCst));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        let window = adapter.window().clone();
+        crate::MockWallet::new("Mock Init")
+            .register(&window)
+            .unwrap();
+        assert!(adapter.get_wallet("Mock Init").is_err());
+
+        InitEvents::new(&window).force_reinit(&mut adapter).unwrap();
+        crate::MockWallet::new("Mock Init")
+            .register(&window)
+            .unwrap();
+        assert!(adapter.get_wallet("Mock Init").is_ok());
+    }
+}
@@ -0,0 +1,140 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::{PublicKeyBytes, SignatureBytes, Utils, WalletError, WalletResult};
+
+/// An Ed25519 public key whose bytes are validated to be a point on the Ed25519 curve.
+/// Prefer this over the [PublicKeyBytes] alias which can hold any 32 bytes.
+/// It is displayed as a Base58 address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PublicKey(VerifyingKey);
+
+impl PublicKey {
+    /// Validate the `bytes` using [Utils::public_key]
+    pub fn new(bytes: PublicKeyBytes) -> WalletResult<Self> {
+        Ok(Self(Utils::public_key(bytes)?))
+    }
+
+    /// Get the [VerifyingKey]
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.0
+    }
+
+    /// Get the bytes of the public key
+    pub fn to_bytes(&self) -> PublicKeyBytes {
+        self.0.to_bytes()
+    }
+}
+
+impl From<VerifyingKey> for PublicKey {
+    fn from(value: VerifyingKey) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = WalletError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::new(Utils::to32byte_array(value)?)
+    }
+}
+
+impl core::fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Utils::address(self.0))
+    }
+}
+
+/// An Ed25519 signature which can only be constructed from exactly 64 bytes.
+/// Prefer this over the [SignatureBytes] alias in new APIs.
+/// The signature is only known to be valid for a message and public key after it is verified
+/// using [Sig::verify]. It is displayed in Base58
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sig(Signature);
+
+impl Sig {
+    /// Create the signature from the `bytes` using [Utils::signature]
+    pub fn new(bytes: SignatureBytes) -> Self {
+        Self(Utils::signature(bytes))
+    }
+
+    /// Get the [Signature]
+    pub fn signature(&self) -> &Signature {
+        &self.0
+    }
+
+    /// Get the bytes of the signature
+    pub fn to_bytes(&self) -> SignatureBytes {
+        self.0.to_bytes()
+    }
+
+    /// Verify the signature of the `message` using the `public_key`.
+    /// See [Utils::verify_signature]
+    pub fn verify(&self, public_key: &PublicKey, message: &[u8]) -> WalletResult<()> {
+        Utils::verify_signature(public_key.0, message, self.0)
+    }
+}
+
+impl From<Signature> for Sig {
+    fn from(value: Signature) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<&[u8]> for Sig {
+    type Error = WalletError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::new(Utils::to64byte_array(value)?))
+    }
+}
+
+impl core::fmt::Display for Sig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Utils::base58_signature(self.0))
+    }
+}
+
+#[cfg(test)]
+mod keys_tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    #[test]
+    fn public_key_and_signature() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let verifying_key = signing_key.verifying_key();
+
+        let public_key = PublicKey::try_from(verifying_key.as_bytes().as_slice()).unwrap();
+        assert_eq!(public_key, PublicKey::from(verifying_key));
+        assert_eq!(public_key.to_string(), Utils::address(verifying_key));
+
+        // `y = 2` is not the y-coordinate of a point on the Ed25519 curve
+        let mut off_curve = [0u8; 32];
+        off_curve[0] = 2;
+        assert_eq!(
+            PublicKey::new(off_curve),
+            Err(WalletError::InvalidEd25519PublicKeyBytes)
+        );
+        assert_eq!(
+            PublicKey::try_from([1u8; 31].as_slice()),
+            Err(WalletError::Expected32ByteLength)
+        );
+
+        let signature = Sig::try_from(signing_key.sign(b"keys").to_bytes().as_slice()).unwrap();
+        assert_eq!(
+            signature.to_string(),
+            Utils::base58_signature(*signature.signature())
+        );
+        assert!(signature.verify(&public_key, b"keys").is_ok());
+        assert_eq!(
+            signature.verify(&public_key, b"other"),
+            Err(WalletError::InvalidSignature)
+        );
+        assert_eq!(
+            Sig::try_from([1u8; 63].as_slice()),
+            Err(WalletError::Expected64ByteLength)
+        );
+    }
+}
@@ -3,11 +3,25 @@
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/JamiiDao/SolanaWalletAdapter/f005b3a555013e2cbfee8825d531b014c180d698/icon.svg"
 )]
-#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![cfg_attr(
+    feature = "browser",
+    doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))
+)]
+#![cfg_attr(
+    not(feature = "browser"),
+    doc = "Solana Wallet Adapter built without the `browser` feature, containing only the cryptographic helpers of [Utils] and [WalletError]"
+)]
 
+#[cfg(feature = "browser")]
 mod adapter;
+#[cfg(feature = "browser")]
 pub use adapter::*;
 
+#[cfg(feature = "browser")]
+mod builder;
+#[cfg(feature = "browser")]
+pub use builder::*;
+
 mod errors;
 pub use errors::*;
 
@@ -17,7 +31,9 @@ pub use commitment::*;
 mod utils;
 pub use utils::*;
 
+#[cfg(feature = "browser")]
 mod events;
+#[cfg(feature = "browser")]
 pub use events::*;
 
 mod constants;
@@ -26,18 +42,48 @@ pub use constants::*;
 mod wallet_ser_der;
 pub use wallet_ser_der::*;
 
+#[cfg(feature = "browser")]
 mod storage;
+#[cfg(feature = "browser")]
 pub use storage::*;
 
+mod transaction;
+pub use transaction::*;
+
+mod keys;
+pub use keys::*;
+
+mod program_address;
+pub use program_address::*;
+
+mod offchain_message;
+pub use offchain_message::*;
+
+#[cfg(all(feature = "browser", any(test, feature = "test-utils")))]
+mod mock_wallet;
+#[cfg(all(feature = "browser", any(test, feature = "test-utils")))]
+pub use mock_wallet::*;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
 // Re-export of crates
+#[cfg(feature = "browser")]
 pub use async_channel;
+#[cfg(feature = "browser")]
 pub use blake3;
 pub use bs58;
 pub use ed25519_dalek;
+#[cfg(feature = "browser")]
+pub use futures_core;
 pub use getrandom;
+#[cfg(feature = "browser")]
 pub use humantime;
 pub use rand_chacha;
 pub use rand_core;
+pub use sha2;
 pub use thiserror;
+#[cfg(feature = "browser")]
 pub use wasm_bindgen_futures;
+#[cfg(feature = "browser")]
 pub use web_sys;
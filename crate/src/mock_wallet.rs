@@ -0,0 +1,411 @@
+use std::{cell::Cell, rc::Rc};
+
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use web_sys::{
+    js_sys::{self, Array, Function, Promise, Reflect},
+    wasm_bindgen::{prelude::Closure, JsCast, JsValue},
+    CustomEvent, CustomEventInit, Window,
+};
+
+use crate::{
+    Cluster, Reflection, Utils, Wallet, WalletError, WalletResult,
+    SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_MESSAGE_IDENTIFIER,
+    SOLANA_SIGN_TRANSACTION_IDENTIFIER, STANDARD_CONNECT_IDENTIFIER,
+    STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER, USER_REJECTED_ERROR_CODE,
+    WALLET_STANDARD_VERSION, WINDOW_REGISTER_WALLET_EVENT_TYPE,
+};
+
+/// A browser wallet implemented in Rust for testing the adapter with `wasm-bindgen-test`
+/// without a browser extension. It builds a JS object that is parsed by [Wallet::from_jsvalue]
+/// and whose feature functions return canned responses:
+/// - `standard:connect` resolves with one account or rejects with [USER_REJECTED_ERROR_CODE].
+///   Silent connect requests can instead be rejected with a JS `Error`
+/// - `standard:disconnect` resolves with `undefined`
+/// - `standard:events` returns an unsubscribe function and never emits events
+/// - `solana:signMessage` and `solana:signAndSendTransaction` sign the message or transaction
+///   bytes using the signing key of the account
+/// - `solana:signTransaction` returns the transaction of the first input unchanged
+///
+/// `solana:signIn` is not mocked. Requires the `browser` and `test-utils` features.
+/// #### Example
+/// ```rust,no_run
+/// use wallet_adapter::{MockWallet, WalletAdapter};
+///
+/// let adapter = WalletAdapter::init().unwrap();
+/// MockWallet::new("Mock").register(adapter.window()).unwrap();
+/// assert!(adapter.get_wallet("Mock").is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockWallet {
+    name: String,
+    version: String,
+    chains: Vec<String>,
+    features: Vec<String>,
+    signing_key: SigningKey,
+    reject_connect: bool,
+    silent_connect_rejection: Option<String>,
+    connect_calls: Rc<Cell<u32>>,
+}
+
+impl MockWallet {
+    /// Create a wallet named `name` on devnet supporting all the mocked features
+    /// with an account whose keypair is random
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            version: WALLET_STANDARD_VERSION.to_string(),
+            chains: vec![Cluster::DevNet.chain().to_string()],
+            features: [
+                STANDARD_CONNECT_IDENTIFIER,
+                STANDARD_DISCONNECT_IDENTIFIER,
+                STANDARD_EVENTS_IDENTIFIER,
+                SOLANA_SIGN_MESSAGE_IDENTIFIER,
+                SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+                SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+            ]
+            .map(String::from)
+            .to_vec(),
+            signing_key: Utils::keypair_rand().0,
+            reject_connect: false,
+            silent_connect_rejection: None,
+            connect_calls: Rc::default(),
+        }
+    }
+
+    /// A wallet named `name` whose connect requests are rejected as if the user
+    /// rejected them, to test the [WalletError::UserRejected] path
+    pub fn rejecting_connect(name: &str) -> Self {
+        let mut wallet = Self::new(name);
+        wallet.set_reject_connect(true);
+
+        wallet
+    }
+
+    /// The version of the wallet standard the wallet implements
+    pub fn set_version(&mut self, version: &str) -> &mut Self {
+        self.version = version.to_string();
+
+        self
+    }
+
+    /// The chains supported by the wallet and its account, for example `solana:devnet`
+    pub fn set_chains(&mut self, chains: &[&str]) -> &mut Self {
+        self.chains = chains.iter().map(|chain| chain.to_string()).collect();
+
+        self
+    }
+
+    /// The identifiers of the features supported by the wallet and its account,
+    /// for example `solana:signMessage`. Only the mocked features can be used
+    pub fn set_features(&mut self, features: &[&str]) -> &mut Self {
+        self.features = features.iter().map(|feature| feature.to_string()).collect();
+
+        self
+    }
+
+    /// The keypair of the account returned by the connect request
+    pub fn set_signing_key(&mut self, signing_key: SigningKey) -> &mut Self {
+        self.signing_key = signing_key;
+
+        self
+    }
+
+    /// Reject connect requests with [USER_REJECTED_ERROR_CODE]
+    pub fn set_reject_connect(&mut self, reject_connect: bool) -> &mut Self {
+        self.reject_connect = reject_connect;
+
+        self
+    }
+
+    /// Reject connect requests with the `silent` input set to `true` with a JS `Error`
+    /// whose message is `message`, like a locked wallet does. Passing `None` resolves them
+    pub fn set_silent_connect_rejection(&mut self, message: Option<&str>) -> &mut Self {
+        self.silent_connect_rejection = message.map(|message| message.to_string());
+
+        self
+    }
+
+    /// Get the name of the wallet
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of connect requests, silent or not, sent to the wallet.
+    /// Clones of the wallet share the count
+    pub fn connect_calls(&self) -> u32 {
+        self.connect_calls.get()
+    }
+
+    /// Get the public key of the account returned by the connect request
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Build the JS object of the wallet as registered by a browser wallet.
+    /// Returns [WalletError::UnsupportedWalletFeature] for features that are not mocked
+    pub fn to_jsvalue(&self) -> WalletResult<JsValue> {
+        let account = self.account()?;
+
+        let mut features = Reflection::new_object();
+        self.features.iter().try_for_each(|feature| {
+            features
+                .set_object(&feature.into(), &self.feature(feature, &account)?)
+                .map(|_| ())
+        })?;
+
+        let mut wallet = Reflection::new_object();
+        wallet.set_object_str("name", &self.name)?;
+        wallet.set_object_str("version", &self.version)?;
+        wallet.set_object(&"chains".into(), &Self::to_array(&self.chains))?;
+        wallet.set_object(&"accounts".into(), &Array::new())?;
+        wallet.set_object(&"features".into(), features.get_inner())?;
+
+        Ok(wallet.take())
+    }
+
+    /// Parse the JS object of the wallet using [Wallet::from_jsvalue]
+    pub fn to_wallet(&self) -> WalletResult<Wallet> {
+        Wallet::from_jsvalue(self.to_jsvalue()?)
+    }
+
+    /// Register the wallet with the adapters listening on the `window` by dispatching
+    /// the `wallet-standard:register-wallet` event like a browser wallet does
+    pub fn register(&self, window: &Window) -> WalletResult<()> {
+        let wallet = self.to_jsvalue()?;
+
+        let callback =
+            Closure::<dyn Fn(JsValue) -> Result<JsValue, JsValue>>::new(move |api: JsValue| {
+                Reflect::get(&api, &"register".into())?
+                    .dyn_into::<Function>()?
+                    .call1(&JsValue::null(), &wallet)
+            })
+            .into_js_value();
+
+        let event_init = CustomEventInit::new();
+        event_init.set_detail(&callback);
+
+        let event =
+            CustomEvent::new_with_event_init_dict(WINDOW_REGISTER_WALLET_EVENT_TYPE, &event_init)?;
+        window.dispatch_event(&event)?;
+
+        Ok(())
+    }
+
+    fn account(&self) -> WalletResult<JsValue> {
+        let verifying_key = self.verifying_key();
+
+        let mut account = Reflection::new_object();
+        account.set_object_str("address", &Utils::address(verifying_key))?;
+        account.set_object(
+            &"publicKey".into(),
+            &Utils::to_uint8array(verifying_key.as_bytes()),
+        )?;
+        account.set_object(&"chains".into(), &Self::to_array(&self.chains))?;
+        account.set_object(&"features".into(), &Self::to_array(&self.features))?;
+
+        Ok(account.take())
+    }
+
+    fn feature(&self, feature: &str, account: &JsValue) -> WalletResult<JsValue> {
+        let mut object = Reflection::new_object();
+        object.set_object_str("version", WALLET_STANDARD_VERSION)?;
+
+        let (key, callback) = match feature {
+            STANDARD_CONNECT_IDENTIFIER => ("connect", self.connect_fn(account)?),
+            STANDARD_DISCONNECT_IDENTIFIER => (
+                "disconnect",
+                Closure::<dyn Fn() -> Promise>::new(|| Promise::resolve(&JsValue::undefined()))
+                    .into_js_value(),
+            ),
+            STANDARD_EVENTS_IDENTIFIER => (
+                "on",
+                Closure::<dyn Fn(JsValue, JsValue) -> JsValue>::new(|_, _| {
+                    Closure::<dyn Fn()>::new(|| {}).into_js_value()
+                })
+                .into_js_value(),
+            ),
+            SOLANA_SIGN_MESSAGE_IDENTIFIER => {
+                ("signMessage", self.sign_fn("message", "signedMessage"))
+            }
+            SOLANA_SIGN_TRANSACTION_IDENTIFIER => ("signTransaction", Self::sign_tx_fn()),
+            SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER => {
+                ("signAndSendTransaction", self.sign_fn("transaction", ""))
+            }
+            _ => return Err(WalletError::UnsupportedWalletFeature(feature.to_string())),
+        };
+
+        if key == "signTransaction" || key == "signAndSendTransaction" {
+            let versions = Array::of2(&"legacy".into(), &JsValue::from_f64(0f64));
+            object.set_object(&"supportedTransactionVersions".into(), &versions)?;
+        }
+        object.set_object(&key.into(), &callback)?;
+
+        Ok(object.take())
+    }
+
+    fn connect_fn(&self, account: &JsValue) -> WalletResult<JsValue> {
+        let rejection = if self.reject_connect {
+            let mut rejection = Reflection::new_object();
+            rejection.set_object(
+                &"code".into(),
+                &JsValue::from_f64(USER_REJECTED_ERROR_CODE as f64),
+            )?;
+            rejection.set_object_str("message", "User rejected the request.")?;
+
+            Some(rejection.take())
+        } else {
+            None
+        };
+        let silent_rejection = self.silent_connect_rejection.clone();
+        let connect_calls = self.connect_calls.clone();
+
+        let mut outcome = Reflection::new_object();
+        outcome.set_object(&"accounts".into(), &Array::of1(account))?;
+        let outcome = outcome.take();
+
+        Ok(
+            Closure::<dyn Fn(JsValue) -> Promise>::new(move |input: JsValue| {
+                connect_calls.set(connect_calls.get() + 1);
+
+                let silent = Reflect::get(&input, &"silent".into())
+                    .ok()
+                    .and_then(|silent| silent.as_bool())
+                    .unwrap_or_default();
+
+                match (silent_rejection.as_ref(), rejection.as_ref()) {
+                    (Some(message), _) if silent => {
+                        Promise::reject(&js_sys::Error::new(message).into())
+                    }
+                    (_, Some(rejection)) => Promise::reject(rejection),
+                    _ => Promise::resolve(&outcome),
+                }
+            })
+            .into_js_value(),
+        )
+    }
+
+    /// Sign the bytes of the `input_key` of the input and resolve with the signature,
+    /// echoing the bytes as the `output_key` if it is not empty
+    fn sign_fn(&self, input_key: &'static str, output_key: &'static str) -> JsValue {
+        let signing_key = self.signing_key.clone();
+
+        Closure::<dyn Fn(JsValue) -> Result<JsValue, JsValue>>::new(move |input: JsValue| {
+            let bytes =
+                Reflect::get(&input, &input_key.into())?.dyn_into::<js_sys::Uint8Array>()?;
+            let signature = signing_key.sign(&bytes.to_vec());
+
+            let output = js_sys::Object::new();
+            Reflect::set(
+                &output,
+                &"signature".into(),
+                &Utils::to_uint8array(&signature.to_bytes()),
+            )?;
+            if !output_key.is_empty() {
+                Reflect::set(&output, &output_key.into(), &bytes)?;
+            }
+
+            Ok(Promise::resolve(&Array::of1(&output)).into())
+        })
+        .into_js_value()
+    }
+
+    /// Resolve with the transaction of the input unchanged
+    fn sign_tx_fn() -> JsValue {
+        Closure::<dyn Fn(JsValue) -> Result<JsValue, JsValue>>::new(|input: JsValue| {
+            let output = js_sys::Object::new();
+            Reflect::set(
+                &output,
+                &"signedTransaction".into(),
+                &Reflect::get(&input, &"transaction".into())?,
+            )?;
+
+            Ok(Promise::resolve(&Array::of1(&output)).into())
+        })
+        .into_js_value()
+    }
+
+    fn to_array(values: &[String]) -> Array {
+        values.iter().map(JsValue::from).collect()
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod mock_wallet_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn to_wallet() {
+        let wallet = MockWallet::new("Mock").to_wallet().unwrap();
+
+        assert_eq!(wallet.name(), "Mock");
+        assert!(wallet.devnet());
+        assert!(wallet.standard_connect());
+        assert!(wallet.solana_sign_and_send_transaction());
+        assert!(!wallet.solana_signin());
+        assert_eq!(wallet.authorized_accounts(), Ok(Vec::new()));
+        assert!(wallet
+            .feature(STANDARD_CONNECT_IDENTIFIER)
+            .unwrap()
+            .get_function("connect")
+            .is_ok());
+        assert_eq!(
+            wallet.feature_version(STANDARD_CONNECT_IDENTIFIER),
+            Ok(WALLET_STANDARD_VERSION.to_string())
+        );
+        assert_eq!(
+            wallet.feature("vendor:stake").map(Reflection::take),
+            Err(WalletError::ExpectedValueNotFound(
+                "vendor:stake".to_string()
+            ))
+        );
+
+        assert_eq!(
+            MockWallet::new("Mock")
+                .set_features(&["solana:signIn"])
+                .to_jsvalue(),
+            Err(WalletError::UnsupportedWalletFeature(
+                "solana:signIn".to_string()
+            ))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn register_connect_and_sign() {
+        let mut adapter = crate::WalletAdapter::init().unwrap();
+        let window = adapter.window().clone();
+        crate::InitEvents::new(&window)
+            .force_reinit(&mut adapter)
+            .unwrap();
+
+        let mock_wallet = MockWallet::new("Mock Register");
+        mock_wallet.register(&window).unwrap();
+
+        let wallet = adapter.get_wallet("Mock Register").unwrap();
+        let connected_account = adapter.connect(wallet).await.unwrap();
+        assert_eq!(
+            connected_account.public_key(),
+            mock_wallet.verifying_key().to_bytes()
+        );
+        assert!(adapter.is_connected().await);
+
+        let signed_message = adapter.sign_message(b"Hello Mock").await.unwrap();
+        assert_eq!(signed_message.message(), "Hello Mock");
+        assert_eq!(signed_message.public_key(), Ok(mock_wallet.verifying_key()));
+    }
+
+    #[wasm_bindgen_test]
+    async fn rejecting_connect() {
+        let mut adapter = crate::WalletAdapter::init().unwrap();
+        let wallet = MockWallet::rejecting_connect("Mock Rejecting")
+            .to_wallet()
+            .unwrap();
+
+        assert_eq!(
+            adapter.connect(wallet).await,
+            Err(WalletError::UserRejected)
+        );
+        assert!(!adapter.is_connected().await);
+    }
+}
@@ -0,0 +1,268 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::{Utils, WalletError, WalletResult};
+
+/// The signing domain prepended to an off-chain message so it can never be
+/// mistaken for a transaction message
+pub const OFFCHAIN_MESSAGE_SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+
+/// The version of the off-chain message header constructed by [Utils::build_offchain_message]
+pub const OFFCHAIN_MESSAGE_HEADER_VERSION: u8 = 0;
+
+// Signing domain (16) + header version (1) + message format (1) + message length (2)
+const OFFCHAIN_MESSAGE_HEADER_LEN: usize = OFFCHAIN_MESSAGE_SIGNING_DOMAIN.len() + 4;
+
+// The size of a packet that a Ledger hardware wallet can sign
+const PACKET_DATA_SIZE: usize = 1232;
+
+/// The format of the payload of an off-chain message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OffchainMessageFormat {
+    /// Printable ASCII characters, limited to a length that Ledger hardware wallets can sign
+    RestrictedAscii,
+    /// UTF-8 text, limited to a length that Ledger hardware wallets can sign
+    LimitedUtf8,
+    /// UTF-8 text up to the maximum length of an off-chain message
+    ExtendedUtf8,
+}
+
+impl OffchainMessageFormat {
+    /// The maximum length in bytes of a payload in this format
+    pub const fn max_len(&self) -> usize {
+        match self {
+            Self::RestrictedAscii | Self::LimitedUtf8 => {
+                PACKET_DATA_SIZE - OFFCHAIN_MESSAGE_HEADER_LEN
+            }
+            Self::ExtendedUtf8 => u16::MAX as usize - OFFCHAIN_MESSAGE_HEADER_LEN,
+        }
+    }
+
+    /// Check whether the `payload` only contains characters allowed in this format
+    pub fn is_valid(&self, payload: &[u8]) -> bool {
+        match self {
+            Self::RestrictedAscii => payload.iter().all(|byte| (0x20..=0x7e).contains(byte)),
+            Self::LimitedUtf8 | Self::ExtendedUtf8 => core::str::from_utf8(payload).is_ok(),
+        }
+    }
+}
+
+impl From<OffchainMessageFormat> for u8 {
+    fn from(value: OffchainMessageFormat) -> Self {
+        match value {
+            OffchainMessageFormat::RestrictedAscii => 0,
+            OffchainMessageFormat::LimitedUtf8 => 1,
+            OffchainMessageFormat::ExtendedUtf8 => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for OffchainMessageFormat {
+    type Error = WalletError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::RestrictedAscii),
+            1 => Ok(Self::LimitedUtf8),
+            2 => Ok(Self::ExtendedUtf8),
+            _ => Err(WalletError::InvalidOffchainMessage(format!(
+                "Unknown message format `{value}`"
+            ))),
+        }
+    }
+}
+
+impl Utils {
+    /// Construct the bytes of a version `0` off-chain message as specified by the Solana
+    /// off-chain message signing proposal. The bytes are the [OFFCHAIN_MESSAGE_SIGNING_DOMAIN],
+    /// the header version, the `format`, the length of the `payload` as little-endian `u16`
+    /// and the `payload`. These are the bytes to sign, for example with
+    /// `WalletAccount::sign_message`, so the signature can be verified by programs
+    /// expecting the standard off-chain message envelope.
+    /// Returns [WalletError::OffchainMessageTooLong] if the `payload` exceeds
+    /// [OffchainMessageFormat::max_len] and [WalletError::InvalidOffchainMessage]
+    /// if the `payload` is empty or contains characters not allowed by the `format`
+    pub fn build_offchain_message(
+        payload: &[u8],
+        format: OffchainMessageFormat,
+    ) -> WalletResult<Vec<u8>> {
+        if payload.len() > format.max_len() {
+            return Err(WalletError::OffchainMessageTooLong {
+                length: payload.len(),
+                max: format.max_len(),
+            });
+        }
+
+        if payload.is_empty() {
+            return Err(WalletError::InvalidOffchainMessage(
+                "The payload is empty".to_string(),
+            ));
+        }
+
+        if !format.is_valid(payload) {
+            return Err(WalletError::InvalidOffchainMessage(format!(
+                "The payload contains characters not allowed in the `{format:?}` format"
+            )));
+        }
+
+        let mut message = Vec::with_capacity(OFFCHAIN_MESSAGE_HEADER_LEN + payload.len());
+        message.extend_from_slice(OFFCHAIN_MESSAGE_SIGNING_DOMAIN);
+        message.push(OFFCHAIN_MESSAGE_HEADER_VERSION);
+        message.push(format.into());
+        message.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        message.extend_from_slice(payload);
+
+        Ok(message)
+    }
+
+    /// Validate the header of the bytes of a version `0` off-chain message
+    /// constructed like [Utils::build_offchain_message] and return the format and the payload.
+    /// Returns [WalletError::InvalidOffchainMessage] if the signing domain, header version,
+    /// format or length of the header are invalid or the payload is not valid for the format
+    pub fn parse_offchain_message(
+        message_envelope: &[u8],
+    ) -> WalletResult<(OffchainMessageFormat, &[u8])> {
+        let invalid = |reason: &str| WalletError::InvalidOffchainMessage(reason.to_string());
+
+        let (header, payload) = message_envelope
+            .split_at_checked(OFFCHAIN_MESSAGE_HEADER_LEN)
+            .ok_or(invalid("The message is shorter than the header"))?;
+        let (signing_domain, header) = header.split_at(OFFCHAIN_MESSAGE_SIGNING_DOMAIN.len());
+
+        if signing_domain != OFFCHAIN_MESSAGE_SIGNING_DOMAIN {
+            return Err(invalid(
+                "The message does not start with the signing domain",
+            ));
+        }
+
+        if header[0] != OFFCHAIN_MESSAGE_HEADER_VERSION {
+            return Err(WalletError::InvalidOffchainMessage(format!(
+                "Unsupported header version `{}`",
+                header[0]
+            )));
+        }
+
+        let format = OffchainMessageFormat::try_from(header[1])?;
+
+        if u16::from_le_bytes([header[2], header[3]]) as usize != payload.len() {
+            return Err(invalid(
+                "The length in the header does not match the length of the payload",
+            ));
+        }
+
+        if payload.len() > format.max_len() {
+            return Err(WalletError::OffchainMessageTooLong {
+                length: payload.len(),
+                max: format.max_len(),
+            });
+        }
+
+        if payload.is_empty() || !format.is_valid(payload) {
+            return Err(WalletError::InvalidOffchainMessage(format!(
+                "The payload is empty or contains characters not allowed in the `{format:?}` format"
+            )));
+        }
+
+        Ok((format, payload))
+    }
+
+    /// Verify the `signature` of an off-chain message signed by the `public_key`.
+    /// The header of the `message_envelope` is validated using [Utils::parse_offchain_message]
+    /// and the signature is verified over the full envelope, including the header,
+    /// since that is what a wallet signing in the off-chain message format signs
+    pub fn verify_offchain_message(
+        public_key: VerifyingKey,
+        message_envelope: &[u8],
+        signature: Signature,
+    ) -> WalletResult<()> {
+        Self::parse_offchain_message(message_envelope)?;
+
+        Self::verify_signature(public_key, message_envelope, signature)
+    }
+}
+
+#[cfg(test)]
+mod offchain_message_tests {
+    use solana_sdk::offchain_message::OffchainMessage;
+
+    use super::*;
+
+    #[test]
+    fn build_offchain_message() {
+        let payload = b"Sign in to Example";
+
+        assert_eq!(
+            Utils::build_offchain_message(payload, OffchainMessageFormat::RestrictedAscii),
+            Ok(OffchainMessage::new(0, payload)
+                .unwrap()
+                .serialize()
+                .unwrap())
+        );
+
+        let payload = "Signé".repeat(300);
+        assert_eq!(
+            Utils::build_offchain_message(payload.as_bytes(), OffchainMessageFormat::ExtendedUtf8),
+            Ok(OffchainMessage::new(0, payload.as_bytes())
+                .unwrap()
+                .serialize()
+                .unwrap())
+        );
+        assert_eq!(
+            Utils::build_offchain_message(payload.as_bytes(), OffchainMessageFormat::LimitedUtf8),
+            Err(WalletError::OffchainMessageTooLong {
+                length: 1800,
+                max: 1212
+            })
+        );
+
+        assert!(matches!(
+            Utils::build_offchain_message(
+                "Signé".as_bytes(),
+                OffchainMessageFormat::RestrictedAscii
+            ),
+            Err(WalletError::InvalidOffchainMessage(_))
+        ));
+        assert!(matches!(
+            Utils::build_offchain_message(&[], OffchainMessageFormat::LimitedUtf8),
+            Err(WalletError::InvalidOffchainMessage(_))
+        ));
+    }
+
+    #[test]
+    fn verify_offchain_message() {
+        let (signing_key, public_key) = Utils::keypair_rand();
+
+        let envelope =
+            Utils::build_offchain_message(b"Hello", OffchainMessageFormat::RestrictedAscii)
+                .unwrap();
+        let signature = Utils::sign_message(&signing_key, &envelope);
+
+        assert_eq!(
+            Utils::parse_offchain_message(&envelope),
+            Ok((OffchainMessageFormat::RestrictedAscii, b"Hello".as_slice()))
+        );
+        assert!(Utils::verify_offchain_message(public_key, &envelope, signature).is_ok());
+        assert_eq!(
+            Utils::verify_offchain_message(
+                public_key,
+                &envelope,
+                Utils::sign_message(&signing_key, b"Hello")
+            ),
+            Err(WalletError::InvalidSignature)
+        );
+
+        let mut truncated = envelope.clone();
+        truncated.pop();
+        assert!(matches!(
+            Utils::verify_offchain_message(public_key, &truncated, signature),
+            Err(WalletError::InvalidOffchainMessage(_))
+        ));
+        assert!(matches!(
+            Utils::verify_offchain_message(
+                public_key,
+                b"Hello",
+                Utils::sign_message(&signing_key, b"Hello")
+            ),
+            Err(WalletError::InvalidOffchainMessage(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use ed25519_dalek::VerifyingKey;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    PublicKeyBytes, Utils, WalletError, WalletResult, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+/// The maximum number of seeds, including the bump seed, used to derive a program address
+pub const MAX_SEEDS: usize = 16;
+
+/// The maximum length in bytes of a seed used to derive a program address
+pub const MAX_SEED_LEN: usize = 32;
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+impl Utils {
+    /// Derive the associated token account address of the `wallet` for the `mint`
+    /// owned by the SPL Token program.
+    /// The addresses are returned as bytes instead of a [VerifyingKey] since program
+    /// derived addresses, like the associated token account address and the
+    /// address of a wallet owned by a program, are not points on the Ed25519 curve
+    pub fn associated_token_address(
+        wallet: &PublicKeyBytes,
+        mint: &PublicKeyBytes,
+    ) -> WalletResult<PublicKeyBytes> {
+        let token_program_id = Self::program_id(TOKEN_PROGRAM_ID)?;
+        let associated_token_program_id = Self::program_id(ASSOCIATED_TOKEN_PROGRAM_ID)?;
+
+        Self::find_program_address(
+            &[wallet, &token_program_id, mint],
+            &associated_token_program_id,
+        )
+        .map(|(address, _bump)| address)
+    }
+
+    /// Find a program derived address (PDA) for the `seeds` and `program_id`
+    /// by trying the bump seeds from 255 down to 0 until the SHA-256 hash
+    /// of the seeds, the bump seed, the program id and `ProgramDerivedAddress`
+    /// is off the Ed25519 curve.
+    /// Returns the address and the bump seed used to derive it.
+    /// At most 15 seeds of at most 32 bytes each can be used,
+    /// otherwise [WalletError::InvalidProgramAddressSeeds] is returned
+    pub fn find_program_address(
+        seeds: &[&[u8]],
+        program_id: &PublicKeyBytes,
+    ) -> WalletResult<(PublicKeyBytes, u8)> {
+        if seeds.len() >= MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+            return Err(WalletError::InvalidProgramAddressSeeds);
+        }
+
+        (0..=u8::MAX)
+            .rev()
+            .find_map(|bump| {
+                let mut hasher = Sha256::new();
+                seeds.iter().for_each(|seed| hasher.update(seed));
+                hasher.update([bump]);
+                hasher.update(program_id);
+                hasher.update(PDA_MARKER);
+
+                let address: PublicKeyBytes = hasher.finalize().into();
+
+                // A program address must not have a corresponding private key
+                VerifyingKey::from_bytes(&address)
+                    .is_err()
+                    .then_some((address, bump))
+            })
+            .ok_or(WalletError::ProgramAddressNotFound)
+    }
+
+    fn program_id(address: &str) -> WalletResult<PublicKeyBytes> {
+        let bytes = bs58::decode(address).into_vec()?;
+
+        Self::to32byte_array(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod program_address_tests {
+    use std::str::FromStr;
+
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    #[test]
+    fn associated_token_address() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                wallet.as_ref(),
+                Pubkey::from_str(TOKEN_PROGRAM_ID).unwrap().as_ref(),
+                mint.as_ref(),
+            ],
+            &Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).unwrap(),
+        );
+
+        assert_eq!(
+            Utils::associated_token_address(&wallet.to_bytes(), &mint.to_bytes()),
+            Ok(expected.to_bytes())
+        );
+    }
+
+    #[test]
+    fn find_program_address() {
+        let program_id = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[b"vault", &[7u8; 32]];
+
+        let (expected_address, expected_bump) = Pubkey::find_program_address(seeds, &program_id);
+        assert_eq!(
+            Utils::find_program_address(seeds, &program_id.to_bytes()),
+            Ok((expected_address.to_bytes(), expected_bump))
+        );
+
+        assert_eq!(
+            Utils::find_program_address(&[&[0u8; 33]], &program_id.to_bytes()),
+            Err(WalletError::InvalidProgramAddressSeeds)
+        );
+        assert_eq!(
+            Utils::find_program_address(&[b"seed".as_slice(); MAX_SEEDS], &program_id.to_bytes()),
+            Err(WalletError::InvalidProgramAddressSeeds)
+        );
+    }
+}
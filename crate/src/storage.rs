@@ -8,32 +8,155 @@ pub type StorageSchema = HashMap<blake3::Hash, Wallet>;
 /// Convenience type for `Rc<RefCell<StorageSchema>>;`
 pub type StorageType = Rc<RefCell<StorageSchema>>;
 
-/// Storage used by the [crate::WalletAdapter]
+/// Storage used by the [crate::WalletAdapter].
+/// Wallets are keyed by the [blake3] hash of their name.
 #[derive(Default, PartialEq, Eq, Clone)]
-pub struct WalletStorage(StorageType);
+pub struct WalletStorage {
+    wallets: StorageType,
+    case_sensitive_names: bool,
+}
 
 impl WalletStorage {
+    /// Create a new empty storage.
+    ///
+    /// By default (`case_sensitive_names` is `false`) wallets are keyed by their lowercase name
+    /// so a wallet that registers itself more than once with a different casing of its name
+    /// (e.g. `solflare` and `Solflare` injected by different sources) is deduplicated into one entry
+    /// and lookups with [Self::get_wallet] are case-insensitive.
+    /// If `case_sensitive_names` is `true` wallets are keyed by the exact bytes of their name
+    /// so wallets whose names only differ by case are stored as separate entries,
+    /// at the cost of losing this deduplication and requiring the exact name for lookups.
+    pub fn new(case_sensitive_names: bool) -> Self {
+        Self {
+            wallets: StorageType::default(),
+            case_sensitive_names,
+        }
+    }
+
     /// Clone the inner field  as `Rc<RefCell<HashMap<blake3::Hash, Wallet>>>`
     pub fn clone_inner(&self) -> StorageType {
-        Rc::clone(&self.0)
+        Rc::clone(&self.wallets)
+    }
+
+    /// Check if wallet names are case-sensitive when keying the storage
+    pub fn case_sensitive_names(&self) -> bool {
+        self.case_sensitive_names
+    }
+
+    /// Get the key a wallet named `wallet_name` is stored under
+    pub fn key(&self, wallet_name: &str) -> blake3::Hash {
+        if self.case_sensitive_names {
+            blake3::hash(wallet_name.as_bytes())
+        } else {
+            blake3::hash(wallet_name.to_lowercase().as_bytes())
+        }
     }
 
     /// Get all the wallets from storage
     pub fn get_wallets(&self) -> Vec<Wallet> {
-        self.0.borrow().values().cloned().collect::<Vec<Wallet>>()
+        self.wallets
+            .borrow()
+            .values()
+            .cloned()
+            .collect::<Vec<Wallet>>()
+    }
+
+    /// Clone the registered wallets into an owned [Vec] sorted by name so the wallets can be
+    /// iterated, for example while rendering a wallet picker, without holding the borrow
+    /// of the storage that a wallet registering at the same time needs.
+    /// Unlike [Self::get_wallets] the order is stable between calls
+    pub fn snapshot(&self) -> Vec<Wallet> {
+        let mut wallets = self.get_wallets();
+        wallets.sort();
+
+        wallets
+    }
+
+    /// Get the number of wallets that have been registered.
+    ///
+    /// Wallets that are already injected into the page register themselves
+    /// synchronously while handling the `wallet-standard:app-ready` event dispatched
+    /// by [crate::WalletAdapter::init], so they are counted as soon as initialization returns.
+    /// Browser extensions that are injected after the page has loaded dispatch
+    /// the `wallet-standard:register-wallet` event later, therefore a UI that shows an
+    /// "install a wallet" prompt when this is `0` should re-check after a short delay
+    /// (a few hundred milliseconds is usually enough) or after the user interacts with the page.
+    pub fn len(&self) -> usize {
+        self.wallets.borrow().len()
+    }
+
+    /// Check if no wallets have been registered yet.
+    /// See [Self::len] for details on when wallets are registered.
+    pub fn is_empty(&self) -> bool {
+        self.wallets.borrow().is_empty()
     }
 
-    /// Get a certain wallet by name from storage
+    /// Get a certain wallet by name from storage.
+    /// The lookup is case-insensitive unless the storage was created
+    /// with case-sensitive names, see [Self::new]
     pub fn get_wallet(&self, wallet_name: &str) -> Option<Wallet> {
-        let storage_ref = self.0.borrow();
-        storage_ref
-            .get(&blake3::hash(wallet_name.to_lowercase().as_bytes()))
-            .cloned()
+        let storage_ref = self.wallets.borrow();
+        storage_ref.get(&self.key(wallet_name)).cloned()
+    }
+
+    /// Remove a wallet by name from storage, for example when the browser extension
+    /// is uninstalled or disabled while the page is open.
+    /// The wallet is looked up using the same key it was inserted with, see [Self::key].
+    /// Returns the removed wallet or `None` if no wallet was stored under the name
+    pub fn remove(&mut self, wallet_name: &str) -> Option<Wallet> {
+        let key = self.key(wallet_name);
+
+        self.wallets.borrow_mut().remove(&key)
+    }
+
+    /// Remove all the wallets from storage
+    pub fn clear(&mut self) {
+        self.wallets.borrow_mut().clear()
     }
 }
 
 impl core::fmt::Debug for WalletStorage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", &*self.0.borrow())
+        write!(f, "{:?}", &*self.wallets.borrow())
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod storage_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn remove_and_clear() {
+        let mut storage = WalletStorage::default();
+
+        ["Solflare", "Phantom"].into_iter().for_each(|name| {
+            storage
+                .clone_inner()
+                .borrow_mut()
+                .insert(storage.key(name), Wallet::with_name(name));
+        });
+
+        assert_eq!(
+            storage
+                .remove("solflare")
+                .map(|wallet| wallet.name().to_string()),
+            Some("Solflare".to_string())
+        );
+        assert!(storage.remove("Solflare").is_none());
+        assert_eq!(storage.len(), 1);
+
+        assert_eq!(
+            storage
+                .snapshot()
+                .iter()
+                .map(|wallet| wallet.name())
+                .collect::<Vec<&str>>(),
+            vec!["Phantom"]
+        );
+
+        storage.clear();
+        assert!(storage.is_empty());
     }
 }
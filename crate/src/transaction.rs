@@ -0,0 +1,367 @@
+use ed25519_dalek::VerifyingKey;
+
+use crate::{PublicKeyBytes, Utils, WalletError, WalletResult};
+
+/// The maximum number of accounts a Solana transaction can reference
+/// given the `u8` indexes used by compiled instructions
+const MAX_ACCOUNTS: usize = 256;
+
+/// The length in bytes of an Ed25519 signature in a transaction
+const SIGNATURE_LENGTH: usize = 64;
+
+/// The length in bytes of a public key or blockhash in a transaction
+const HASH_LENGTH: usize = 32;
+
+/// The version of a Solana transaction message
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum TransactionVersion {
+    /// A legacy transaction message without a version prefix
+    Legacy,
+    /// A version zero transaction message which supports address lookup tables
+    V0,
+}
+
+/// Information about a transaction that has been decoded and checked
+/// by [Utils::validate_transaction]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct TxInfo {
+    version: TransactionVersion,
+    signature_count: usize,
+    account_count: usize,
+    fee_payer: PublicKeyBytes,
+}
+
+impl TxInfo {
+    /// The version of the transaction message
+    pub fn version(&self) -> TransactionVersion {
+        self.version
+    }
+
+    /// The number of signatures the transaction carries
+    pub fn signature_count(&self) -> usize {
+        self.signature_count
+    }
+
+    /// The number of static account keys referenced by the transaction message
+    pub fn account_count(&self) -> usize {
+        self.account_count
+    }
+
+    /// The public key of the fee payer which is always the first account key
+    pub fn fee_payer(&self) -> PublicKeyBytes {
+        self.fee_payer
+    }
+
+    /// The Base58 address of the fee payer
+    pub fn fee_payer_address(&self) -> String {
+        bs58::encode(&self.fee_payer).into_string()
+    }
+}
+
+/// A cursor over the bytes of a serialized transaction
+struct TxReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> TxReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn take(&mut self, length: usize, section: &str) -> WalletResult<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(WalletError::MalformedTransaction(format!(
+                "Transaction is truncated while reading the {section}"
+            )))?;
+
+        let taken = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(taken)
+    }
+
+    fn byte(&mut self, section: &str) -> WalletResult<u8> {
+        Ok(self.take(1, section)?[0])
+    }
+
+    fn peek(&self, section: &str) -> WalletResult<u8> {
+        self.bytes
+            .get(self.position)
+            .copied()
+            .ok_or(WalletError::MalformedTransaction(format!(
+                "Transaction is truncated while reading the {section}"
+            )))
+    }
+
+    /// Decode a `compact-u16` (ShortVec) length which is encoded in one to three bytes
+    fn compact_u16(&mut self, section: &str) -> WalletResult<usize> {
+        let mut value = 0usize;
+
+        for index in 0..3 {
+            let byte = self.byte(section)?;
+            value |= ((byte & 0x7f) as usize) << (index * 7);
+
+            if byte & 0x80 == 0 {
+                // Reject alias encodings with trailing zero bytes and values that overflow a u16
+                if (index > 0 && byte == 0) || value > u16::MAX as usize {
+                    break;
+                }
+
+                return Ok(value);
+            }
+        }
+
+        Err(WalletError::MalformedTransaction(format!(
+            "Invalid compact-u16 encoding for the {section}"
+        )))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+}
+
+/// The sections of a serialized transaction that have been checked by [decode_transaction]
+struct DecodedTx<'a> {
+    version: TransactionVersion,
+    signatures: &'a [u8],
+    account_keys: &'a [u8],
+    num_required_signatures: usize,
+}
+
+/// Parse the signatures, message header, account keys, recent blockhash, instructions
+/// and address table lookups (for version zero transactions) of a serialized transaction
+fn decode_transaction(tx: &[u8]) -> WalletResult<DecodedTx<'_>> {
+    let mut reader = TxReader::new(tx);
+
+    let signature_count = reader.compact_u16("signatures length")?;
+    let signatures =
+        reader.take(
+            signature_count.checked_mul(SIGNATURE_LENGTH).ok_or(
+                WalletError::MalformedTransaction("Implausible number of signatures".to_string()),
+            )?,
+            "signatures",
+        )?;
+
+    let version = if reader.peek("message")? & 0x80 != 0 {
+        match reader.byte("message version")? & 0x7f {
+            0 => TransactionVersion::V0,
+            _ => return Err(WalletError::UnsupportedTransactionVersion),
+        }
+    } else {
+        TransactionVersion::Legacy
+    };
+
+    let num_required_signatures = reader.byte("message header")? as usize;
+    let num_readonly_signed = reader.byte("message header")? as usize;
+    let num_readonly_unsigned = reader.byte("message header")? as usize;
+
+    let account_count = reader.compact_u16("account keys length")?;
+
+    if account_count == 0 || account_count > MAX_ACCOUNTS {
+        return Err(WalletError::MalformedTransaction(format!(
+            "Implausible number of account keys `{account_count}`"
+        )));
+    }
+
+    if num_required_signatures == 0
+        || num_required_signatures != signature_count
+        || num_readonly_signed >= num_required_signatures
+        || num_required_signatures + num_readonly_unsigned > account_count
+    {
+        return Err(WalletError::MalformedTransaction(format!(
+                "The message header `[{num_required_signatures}, {num_readonly_signed}, {num_readonly_unsigned}]` does not match `{signature_count}` signatures and `{account_count}` account keys"
+            )));
+    }
+
+    let account_keys = reader.take(account_count * HASH_LENGTH, "account keys")?;
+
+    reader.take(HASH_LENGTH, "recent blockhash")?;
+
+    let instructions_count = reader.compact_u16("instructions length")?;
+    (0..instructions_count).try_for_each(|_| {
+        reader.byte("instruction program id index")?;
+        let accounts_length = reader.compact_u16("instruction accounts length")?;
+        reader.take(accounts_length, "instruction accounts")?;
+        let data_length = reader.compact_u16("instruction data length")?;
+        reader.take(data_length, "instruction data")?;
+
+        Ok::<(), WalletError>(())
+    })?;
+
+    if version == TransactionVersion::V0 {
+        let lookups_count = reader.compact_u16("address table lookups length")?;
+        (0..lookups_count).try_for_each(|_| {
+            reader.take(HASH_LENGTH, "address table lookup account key")?;
+            let writable_length = reader.compact_u16("writable indexes length")?;
+            reader.take(writable_length, "writable indexes")?;
+            let readonly_length = reader.compact_u16("readonly indexes length")?;
+            reader.take(readonly_length, "readonly indexes")?;
+
+            Ok::<(), WalletError>(())
+        })?;
+    }
+
+    if !reader.is_empty() {
+        return Err(WalletError::MalformedTransaction(
+            "Transaction has unexpected trailing bytes".to_string(),
+        ));
+    }
+
+    Ok(DecodedTx {
+        version,
+        signatures,
+        account_keys,
+        num_required_signatures,
+    })
+}
+
+impl Utils {
+    /// Decode a serialized transaction and perform a structural sanity check on it
+    /// before it is sent to a wallet for signing.
+    /// The signatures, message header, account keys, recent blockhash, instructions
+    /// and address table lookups (for version zero transactions) are parsed and
+    /// an error is returned if any section is truncated, has implausible counts
+    /// or if there are trailing bytes.
+    pub fn validate_transaction(tx: &[u8]) -> WalletResult<TxInfo> {
+        let decoded = decode_transaction(tx)?;
+
+        Ok(TxInfo {
+            version: decoded.version,
+            signature_count: decoded.num_required_signatures,
+            account_count: decoded.account_keys.len() / HASH_LENGTH,
+            fee_payer: Utils::to32byte_array(&decoded.account_keys[..HASH_LENGTH])?,
+        })
+    }
+
+    /// Get each required signer of a serialized transaction in the order of
+    /// the account keys together with whether its signature slot is present.
+    /// A signature slot that is all zeros is a placeholder for a signer
+    /// that has not signed yet which is useful when inspecting partially signed
+    /// multi-sig transactions. The transaction is checked using the same rules as
+    /// [Utils::validate_transaction].
+    pub fn signer_status(tx: &[u8]) -> WalletResult<Vec<(VerifyingKey, bool)>> {
+        let decoded = decode_transaction(tx)?;
+
+        decoded
+            .account_keys
+            .chunks_exact(HASH_LENGTH)
+            .zip(decoded.signatures.chunks_exact(SIGNATURE_LENGTH))
+            .take(decoded.num_required_signatures)
+            .map(|(public_key, signature)| {
+                let public_key = Utils::public_key(Utils::to32byte_array(public_key)?)?;
+                let is_present = signature.iter().any(|byte| *byte != 0);
+
+                Ok((public_key, is_present))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod transaction_tests {
+    use solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::{v0, Message, VersionedMessage},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        transaction::{Transaction, VersionedTransaction},
+    };
+
+    use super::*;
+
+    fn transfer_instruction(payer: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[2, 0, 0, 0, 232, 3, 0, 0, 0, 0, 0, 0],
+            vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ],
+        )
+    }
+
+    fn legacy_fixture() -> (Pubkey, Vec<u8>) {
+        let payer = Keypair::new();
+        let instruction = transfer_instruction(&payer.pubkey());
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let tx = Transaction::new(&[&payer], message, Hash::new_unique());
+
+        (payer.pubkey(), bincode::serialize(&tx).unwrap())
+    }
+
+    #[test]
+    fn valid_legacy_transaction() {
+        let (payer, tx_bytes) = legacy_fixture();
+
+        let tx_info = Utils::validate_transaction(&tx_bytes).unwrap();
+
+        assert_eq!(tx_info.version(), TransactionVersion::Legacy);
+        assert_eq!(tx_info.signature_count(), 1);
+        assert_eq!(tx_info.account_count(), 3);
+        assert_eq!(tx_info.fee_payer(), payer.to_bytes());
+        assert_eq!(tx_info.fee_payer_address(), payer.to_string());
+    }
+
+    #[test]
+    fn valid_v0_transaction() {
+        let payer = Keypair::new();
+        let instruction = transfer_instruction(&payer.pubkey());
+        let message =
+            v0::Message::try_compile(&payer.pubkey(), &[instruction], &[], Hash::new_unique())
+                .unwrap();
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap();
+        let tx_bytes = bincode::serialize(&tx).unwrap();
+
+        let tx_info = Utils::validate_transaction(&tx_bytes).unwrap();
+
+        assert_eq!(tx_info.version(), TransactionVersion::V0);
+        assert_eq!(tx_info.signature_count(), 1);
+        assert_eq!(tx_info.fee_payer(), payer.pubkey().to_bytes());
+    }
+
+    #[test]
+    fn truncated_transaction() {
+        let (_, tx_bytes) = legacy_fixture();
+
+        assert!(Utils::validate_transaction(&[]).is_err());
+        assert!(Utils::validate_transaction(&tx_bytes[..tx_bytes.len() - 1]).is_err());
+        assert!(Utils::validate_transaction(&tx_bytes[..100]).is_err());
+
+        let mut trailing = tx_bytes.clone();
+        trailing.push(0);
+        assert!(Utils::validate_transaction(&trailing).is_err());
+    }
+
+    #[test]
+    fn partially_signed_signer_status() {
+        let payer = Keypair::new();
+        let co_signer = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(co_signer.pubkey(), true),
+            ],
+        );
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let mut tx = Transaction::new_unsigned(message);
+        tx.partial_sign(&[&payer], Hash::new_unique());
+        let tx_bytes = bincode::serialize(&tx).unwrap();
+
+        let signers = Utils::signer_status(&tx_bytes).unwrap();
+
+        assert_eq!(signers.len(), 2);
+        assert_eq!(signers[0].0.to_bytes(), payer.pubkey().to_bytes());
+        assert!(signers[0].1);
+        assert_eq!(signers[1].0.to_bytes(), co_signer.pubkey().to_bytes());
+        assert!(!signers[1].1);
+    }
+}
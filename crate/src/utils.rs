@@ -1,24 +1,42 @@
 use std::borrow::Cow;
 
+#[cfg(feature = "browser")]
+use crate::SignedMessage;
+use base64::Engine;
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+#[cfg(feature = "browser")]
 use web_sys::{
     js_sys::{self, Array, Function, Object, Reflect},
     wasm_bindgen::{JsCast, JsValue},
 };
 
-use crate::{WalletError, WalletResult};
+use crate::{
+    Cluster, WalletError, WalletResult, SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+    SOLANA_SIGN_IN_IDENTIFIER, SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+    STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
+};
 
-/// A 32 byte array representing a Public Key
+/// A 32 byte array representing a Public Key.
+/// Use [PublicKey](crate::PublicKey) for a public key that is validated
 pub type PublicKeyBytes = [u8; 32];
 
-/// A 64 byte array representing a Signature
+/// A 64 byte array representing a Signature.
+/// Use [Sig](crate::Sig) for a signature that is checked to be 64 bytes
 pub type SignatureBytes = [u8; 64];
 
 /// The Version of the Wallet Standard currently implemented.
 /// This may be used by the app to determine compatibility and feature detect.
 pub const WALLET_STANDARD_VERSION: &str = "1.0.0";
 
-/// Helper utilities
+/// The base URL of the Solana Explorer
+pub const SOLANA_EXPLORER_URL: &str = "https://explorer.solana.com";
+
+/// Helper utilities.
+/// The cryptographic, Base58, Base64, hex and explorer URL helpers do not call into JS
+/// and can be used and tested on native targets, including without the default features.
+/// The helpers that call into JS, for example to convert a `JsValue`, read the clock
+/// or wait using `setTimeout`, are only available with the `browser` feature
+/// and require a wasm32 target at runtime
 pub struct Utils;
 
 impl Utils {
@@ -27,13 +45,39 @@ impl Utils {
         Self::rand_32bytes()
     }
 
+    /// Generate an Ed25519 keypair from random bytes. This is useful for testing
+    /// since the [SigningKey](ed25519_dalek::SigningKey) can sign a message whose signature
+    /// is then checked using [Utils::verify_signature].
+    /// Requires the `test-utils` feature
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn keypair_rand() -> (ed25519_dalek::SigningKey, VerifyingKey) {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&Self::rand_32bytes());
+        let verifying_key = signing_key.verifying_key();
+
+        (signing_key, verifying_key)
+    }
+
+    /// Sign a `message` using the `signing_key`, for example one generated by
+    /// [Utils::keypair_rand], to create signed message fixtures without a browser wallet.
+    /// Requires the `test-utils` feature
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn sign_message(signing_key: &ed25519_dalek::SigningKey, message: &[u8]) -> Signature {
+        use ed25519_dalek::Signer;
+
+        signing_key.sign(message)
+    }
+
     /// Generate a 32 byte array from random bytes
     pub fn rand_32bytes() -> [u8; 32] {
         use rand_chacha::ChaCha20Rng;
-        use rand_core::{RngCore, SeedableRng};
+        use rand_core::SeedableRng;
 
-        let mut rng = ChaCha20Rng::from_os_rng();
+        Self::rand_32bytes_with(&mut ChaCha20Rng::from_os_rng())
+    }
 
+    /// Generate a 32 byte array from the random bytes of the `rng`.
+    /// Passing a seeded RNG generates reproducible bytes, for example for test fixtures
+    pub fn rand_32bytes_with<R: rand_core::RngCore>(rng: &mut R) -> [u8; 32] {
         let mut buffer = [0u8; 32];
 
         rng.fill_bytes(&mut buffer);
@@ -41,6 +85,7 @@ impl Utils {
         buffer
     }
 
+    #[cfg(feature = "browser")]
     /// Convert a [JsValue] error to a [WalletError]
     pub fn jsvalue_to_error<T: core::fmt::Debug>(
         value: Result<T, JsValue>,
@@ -75,6 +120,19 @@ impl Utils {
         bytes.try_into().or(Err(WalletError::Expected64ByteLength))
     }
 
+    #[cfg(feature = "browser")]
+    /// Copy a slice of bytes into a new [js_sys::Uint8Array]. This is useful when
+    /// calling the functions of a browser wallet directly
+    pub fn to_uint8array(bytes: &[u8]) -> js_sys::Uint8Array {
+        js_sys::Uint8Array::from(bytes)
+    }
+
+    #[cfg(feature = "browser")]
+    /// Copy the bytes of a [js_sys::Uint8Array] into a [Vec]
+    pub fn from_uint8array(array: &js_sys::Uint8Array) -> Vec<u8> {
+        array.to_vec()
+    }
+
     /// Verify a [message](str) using a [PublicKey](VerifyingKey) and [Signature]
     pub fn verify_signature(
         public_key: VerifyingKey,
@@ -86,6 +144,66 @@ impl Utils {
             .or(Err(WalletError::InvalidSignature))
     }
 
+    /// Verify a `message` using the bytes of a `public_key` and a `signature`, for example
+    /// when the values cross an FFI boundary as byte slices.
+    /// Returns [WalletError::Expected32ByteLength] or [WalletError::Expected64ByteLength]
+    /// if the slices have the wrong length. See [Self::verify_signature]
+    pub fn verify_signature_bytes(
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> WalletResult<()> {
+        let public_key = Self::public_key(Self::to32byte_array(public_key)?)?;
+        let signature = Self::signature(Self::to64byte_array(signature)?);
+
+        Self::verify_signature(public_key, message, signature)
+    }
+
+    /// Verify a `message` given the Base58 `address` of the signer and the Base58 `signature`.
+    /// This is useful for verifying Sign In With Solana (SIWS) responses
+    /// where the address and signature are transmitted as Base58 strings.
+    pub fn verify_message_base58(
+        address: &str,
+        message: &[u8],
+        signature: &str,
+    ) -> WalletResult<()> {
+        let public_key = Self::validate_base58_address(address)?;
+
+        let signature_bytes = bs58::decode(signature).into_vec()?;
+        let signature = Self::signature(Self::to64byte_array(&signature_bytes)?);
+
+        Self::verify_signature(public_key, message, signature)
+    }
+
+    /// Verify a UTF-8 `message` against the raw bytes of the message and,
+    /// if that fails, against a variant with `\r\n` and `\r` line endings normalized to `\n`.
+    /// This is useful since some wallets normalize line endings before signing a message.
+    ///
+    /// #### Security
+    /// Accepting more than one encoding means a signature over the normalized message
+    /// is also accepted for a message that only differs by its line endings.
+    /// Only use this where the line endings carry no meaning, for example when displaying
+    /// a message to a user, and prefer [Self::verify_signature] for messages that are
+    /// parsed or compared byte for byte like Sign In With Solana (SIWS) messages.
+    pub fn verify_message_lenient(
+        public_key: VerifyingKey,
+        message: &str,
+        signature: Signature,
+    ) -> WalletResult<()> {
+        if Self::verify_signature(public_key, message.as_bytes(), signature).is_ok() {
+            return Ok(());
+        }
+
+        let normalized = message.replace("\r\n", "\n").replace('\r', "\n");
+
+        if normalized == message {
+            return Err(WalletError::InvalidSignature);
+        }
+
+        Self::verify_signature(public_key, normalized.as_bytes(), signature)
+    }
+
+    #[cfg(feature = "browser")]
     /// Convert a [JsValue] to a [Signature]
     pub fn jsvalue_to_signature(value: JsValue, namespace: &str) -> WalletResult<Signature> {
         let in_case_of_error = Err(WalletError::InternalError(format!(
@@ -102,6 +220,42 @@ impl Utils {
         Ok(Self::signature(signature_bytes))
     }
 
+    /// Parse an item of the output of `solana:signMessage` which is an object
+    /// `{ signedMessage, signature, account }`. Some wallets return a `publicKey`
+    /// instead of the `account` so it is used if the `account` is missing.
+    /// The signature is verified against the signed message and the public key before returning
+    #[cfg(feature = "browser")]
+    pub fn parse_sign_message_response(value: JsValue) -> WalletResult<SignedMessage> {
+        Self::parse_sign_message_item(value, None)
+    }
+
+    /// Same as [Self::parse_sign_message_response] but uses the `requested_signer`
+    /// as the public key if the wallet returns neither the `account` nor the `publicKey`
+    #[cfg(feature = "browser")]
+    pub(crate) fn parse_sign_message_item(
+        value: JsValue,
+        requested_signer: Option<[u8; 32]>,
+    ) -> WalletResult<SignedMessage> {
+        let response = Reflection::new(value)?;
+
+        let message = response.reflect_bytes("signedMessage")?;
+        let signature = Self::jsvalue_to_signature(
+            response.reflect_inner("signature")?,
+            "solana:signMessage -> SignedMessageOutput::signature",
+        )?;
+        let public_key = Self::public_key(
+            match (response.get_optional("account")?, requested_signer) {
+                (Some(account), _) => account.byte32array("publicKey")?,
+                (None, Some(requested_signer)) if !response.has("publicKey") => requested_signer,
+                (None, _) => response.byte32array("publicKey")?,
+            },
+        )?;
+
+        Self::verify_signature(public_key, &message, signature)?;
+
+        Ok(SignedMessage::new(message, signature, public_key))
+    }
+
     /// Generate the Base58 address from a [PublicKey](VerifyingKey)
     pub fn address(public_key: VerifyingKey) -> String {
         bs58::encode(public_key.as_ref()).into_string()
@@ -112,40 +266,266 @@ impl Utils {
         bs58::encode(signature.to_bytes()).into_string()
     }
 
-    /// Get the shortened string of the `Base58 string` .
-    /// It displays the first 4 characters and the last for characters
-    /// separated by ellipsis eg `FXdl...RGd4` .
-    /// If the string is less than 8 characters, an error is thrown
-    pub fn shorten_base58(base58_str: &str) -> WalletResult<Cow<str>> {
-        if base58_str.len() < 8 {
-            return Err(WalletError::InvalidBase58Address);
+    /// The Solana Explorer URL of the transaction with the given `signature` on `cluster`
+    pub fn explorer_tx_url(signature: &Signature, cluster: &Cluster) -> String {
+        Self::explorer_url("tx", &Self::base58_signature(*signature), cluster)
+    }
+
+    /// The Solana Explorer URL of the account with the given public key on `cluster`
+    pub fn explorer_address_url(public_key: &VerifyingKey, cluster: &Cluster) -> String {
+        Self::explorer_url("address", &Self::address(*public_key), cluster)
+    }
+
+    /// Mainnet is the default cluster of the explorer so it does not need a `cluster` query.
+    /// Localnet is not known to the explorer so its endpoint is passed as a custom RPC URL
+    fn explorer_url(path: &str, id: &str, cluster: &Cluster) -> String {
+        let query = match cluster {
+            Cluster::MainNet => String::new(),
+            Cluster::DevNet | Cluster::TestNet => "?cluster=".to_string() + cluster.display(),
+            Cluster::LocalNet => {
+                "?cluster=custom&customUrl=".to_string() + &Self::percent_encode(cluster.endpoint())
+            }
+        };
+
+        SOLANA_EXPLORER_URL.to_string() + "/" + path + "/" + id + &query
+    }
+
+    /// Percent encode all characters except the unreserved characters of RFC 3986
+    fn percent_encode(value: &str) -> String {
+        value.bytes().fold(String::new(), |mut encoded, byte| {
+            if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                encoded.push(byte as char);
+            } else {
+                encoded.push_str(&format!("%{byte:02X}"));
+            }
+
+            encoded
+        })
+    }
+
+    /// Generate a hex encoded string from a [PublicKey](VerifyingKey)
+    pub fn public_key_to_hex(public_key: &VerifyingKey) -> String {
+        Self::hex_encode(public_key.as_bytes())
+    }
+
+    /// Generate a hex encoded string from a [Signature]
+    pub fn signature_to_hex(signature: &Signature) -> String {
+        Self::hex_encode(&signature.to_bytes())
+    }
+
+    /// Parse a [PublicKey](VerifyingKey) from a hex encoded string of 32 bytes
+    pub fn public_key_from_hex(hex: &str) -> WalletResult<VerifyingKey> {
+        let bytes = Self::hex_decode(hex)?;
+
+        Self::public_key(Self::to32byte_array(&bytes)?)
+    }
+
+    /// Parse a [Signature] from a hex encoded string of 64 bytes
+    pub fn signature_from_hex(hex: &str) -> WalletResult<Signature> {
+        let bytes = Self::hex_decode(hex)?;
+
+        Ok(Self::signature(Self::to64byte_array(&bytes)?))
+    }
+
+    /// Encode bytes, for example a serialized transaction, as a standard padded Base64 string
+    pub fn base64_encode(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    /// Decode a standard padded Base64 string, for example a serialized transaction
+    /// received from a backend, into bytes
+    pub fn base64_decode(base64_str: &str) -> WalletResult<Vec<u8>> {
+        base64::engine::general_purpose::STANDARD
+            .decode(base64_str)
+            .map_err(|error| WalletError::Base64Decode(error.to_string()))
+    }
+
+    /// Encode bytes as a lowercase hex string
+    pub fn hex_encode(bytes: &[u8]) -> String {
+        const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+        bytes
+            .iter()
+            .flat_map(|byte| {
+                [
+                    HEX_CHARS[(byte >> 4) as usize] as char,
+                    HEX_CHARS[(byte & 0x0f) as usize] as char,
+                ]
+            })
+            .collect()
+    }
+
+    /// Decode a hex string, either lowercase or uppercase, into bytes
+    pub fn hex_decode(hex: &str) -> WalletResult<Vec<u8>> {
+        let invalid_hex = || WalletError::InvalidHexString(hex.to_string());
+
+        if hex.len() % 2 != 0 {
+            return Err(invalid_hex());
         }
 
-        let first_part = &base58_str[..4];
-        let last_part = &base58_str[base58_str.len() - 4..];
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let high = (pair[0] as char).to_digit(16).ok_or_else(invalid_hex)?;
+                let low = (pair[1] as char).to_digit(16).ok_or_else(invalid_hex)?;
 
-        Ok(Cow::Borrowed(first_part) + "..." + last_part)
+                Ok((high << 4 | low) as u8)
+            })
+            .collect()
+    }
+
+    /// Get the shortened string of the `Base58 string` .
+    /// It displays the first 4 characters and the last for characters
+    /// separated by ellipsis eg `FXdl...RGd4` .
+    /// If the string is less than 8 characters or is not ASCII, an error is thrown
+    pub fn shorten_base58(base58_str: &str) -> WalletResult<Cow<'_, str>> {
+        Self::custom_shorten_base58(base58_str, 4)
     }
 
     /// Same as [Self::shorten_base58] but with a custom range
     /// instead of taking the first 4 character and the last 4 characters
     /// it uses a custom range.
-    pub fn custom_shorten_base58(base58_str: &str, take: usize) -> WalletResult<Cow<str>> {
-        if base58_str.len() < take + take {
+    pub fn custom_shorten_base58(base58_str: &str, take: usize) -> WalletResult<Cow<'_, str>> {
+        Self::shorten_base58_asymmetric(base58_str, take, take)
+    }
+
+    /// Same as [Self::custom_shorten_base58] but takes `take_start` characters
+    /// from the start and `take_end` characters from the end, eg `FXdlWq...RGd4`.
+    /// If the string has less than `take_start + take_end` characters
+    /// or is not ASCII, an error is thrown
+    pub fn shorten_base58_asymmetric(
+        base58_str: &str,
+        take_start: usize,
+        take_end: usize,
+    ) -> WalletResult<Cow<'_, str>> {
+        // Base58 is ASCII, checking it ensures slicing by byte index never splits a character
+        if !base58_str.is_ascii() || base58_str.len() < take_start.saturating_add(take_end) {
             return Err(WalletError::InvalidBase58Address);
         }
 
-        let first_part = &base58_str[..take];
-        let last_part = &base58_str[base58_str.len() - take..];
+        let first_part = &base58_str[..take_start];
+        let last_part = &base58_str[base58_str.len() - take_end..];
 
         Ok(Cow::Borrowed(first_part) + "..." + last_part)
     }
+
+    /// Parse a Base58 address into a [PublicKey](VerifyingKey).
+    /// This checks that the address decodes to 32 bytes and that the bytes
+    /// are a valid Ed25519 curve point, not just that the length is correct
+    pub fn validate_base58_address(address: &str) -> WalletResult<VerifyingKey> {
+        let public_key_bytes = bs58::decode(address)
+            .into_vec()
+            .or(Err(WalletError::InvalidBase58Address))?;
+
+        Self::public_key(Self::to32byte_array(&public_key_bytes)?)
+    }
+
+    /// Check that the `input` Base58 decodes to exactly 32 bytes.
+    /// Unlike [Self::validate_base58_address] the bytes are not checked
+    /// to be a valid Ed25519 curve point which makes this a cheap check for form validation
+    pub fn is_valid_base58_address(input: &str) -> bool {
+        let mut buffer = [0u8; 32];
+
+        matches!(bs58::decode(input).onto(&mut buffer), Ok(32))
+    }
+
+    /// Validate a Base58 address using [Self::validate_base58_address] and then shorten it
+    /// using [Self::custom_shorten_base58] so that a shortened form of an invalid address
+    /// is never displayed
+    pub fn validate_and_shorten(address: &str, take: usize) -> WalletResult<Cow<'_, str>> {
+        Self::validate_base58_address(address)?;
+
+        Self::custom_shorten_base58(address, take)
+    }
+
+    /// A preview of a message to be signed for display in a confirmation UI.
+    /// A UTF-8 message is truncated to `max_len` characters and any other message
+    /// is rendered as the hex of its first `max_len` bytes.
+    /// An ellipsis `...` is appended if the message was truncated
+    pub fn message_preview(message: &[u8], max_len: usize) -> String {
+        let (mut preview, truncated) = match core::str::from_utf8(message) {
+            Ok(text) => match text.char_indices().nth(max_len) {
+                Some((boundary, _)) => (text[..boundary].to_string(), true),
+                None => (text.to_string(), false),
+            },
+            Err(_) => (
+                Self::hex_encode(&message[..message.len().min(max_len)]),
+                message.len() > max_len,
+            ),
+        };
+
+        if truncated {
+            preview.push_str("...");
+        }
+
+        preview
+    }
+
+    /// Map a feature name advertised by a wallet to the canonical Wallet Standard identifier.
+    /// The comparison ignores casing and `-` or `_` separators so that legacy aliases like
+    /// `solana:sign-message` or `Solana:SignMessage` map to `solana:signMessage`.
+    /// Returns `None` if the feature is not a known `standard:` or `solana:` feature.
+    pub fn canonical_feature_name(raw: &str) -> Option<&'static str> {
+        const CANONICAL_FEATURES: [&str; 7] = [
+            STANDARD_CONNECT_IDENTIFIER,
+            STANDARD_DISCONNECT_IDENTIFIER,
+            STANDARD_EVENTS_IDENTIFIER,
+            SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+            SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+            SOLANA_SIGN_IN_IDENTIFIER,
+            SOLANA_SIGN_MESSAGE_IDENTIFIER,
+        ];
+
+        let normalize = |value: &str| {
+            value
+                .trim()
+                .chars()
+                .filter(|character| *character != '-' && *character != '_')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+
+        let normalized = normalize(raw);
+
+        CANONICAL_FEATURES
+            .into_iter()
+            .find(|feature| normalize(feature) == normalized)
+    }
+
+    #[cfg(feature = "browser")]
+    /// The current time in milliseconds since the UNIX epoch as reported by
+    /// [JavaScript Date Now](js_sys::Date::now). Requires the `wasm32` target
+    pub fn now_millis() -> f64 {
+        js_sys::Date::now()
+    }
+
+    #[cfg(feature = "browser")]
+    /// Wait for `millis` milliseconds using the `setTimeout` of the browser window
+    /// without blocking the browser. Requires the `wasm32` target
+    pub async fn sleep(millis: u32) -> WalletResult<()> {
+        let window = web_sys::window().ok_or(WalletError::MissingAccessToBrowserWindow)?;
+
+        let mut timeout_handle = Ok(0);
+        let timeout = js_sys::Promise::new(&mut |resolve, _reject| {
+            timeout_handle = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                &resolve,
+                millis.min(i32::MAX as u32) as i32,
+            );
+        });
+        timeout_handle?;
+
+        wasm_bindgen_futures::JsFuture::from(timeout).await?;
+
+        Ok(())
+    }
 }
 
+#[cfg(feature = "browser")]
 /// Perform reflection on a [JsValue]
 #[derive(Debug)]
 pub struct Reflection(JsValue);
 
+#[cfg(feature = "browser")]
 impl Reflection {
     /// Initialize [Reflection] and check if the value is null or undefined
     pub fn new(value: JsValue) -> WalletResult<Self> {
@@ -162,6 +542,24 @@ impl Reflection {
         Reflection::new(inner)
     }
 
+    /// Initialize [Reflection] by reflecting each segment of the `path` in turn,
+    /// eg `&["features", "solana:signMessage", "signMessage"]`.
+    /// Returns [WalletError::ExpectedValueNotFound] containing the failing segment
+    /// if a segment is null or undefined
+    pub fn new_from_path(value: &JsValue, path: &[&str]) -> WalletResult<Self> {
+        let mut inner = value.clone();
+
+        for segment in path {
+            inner = Reflect::get(&inner, &(*segment).into())?;
+
+            if inner.is_undefined() || inner.is_null() {
+                return Err(WalletError::ExpectedValueNotFound(segment.to_string()));
+            }
+        }
+
+        Reflection::new(inner)
+    }
+
     /// Initialize [Reflection] from a [js_sys::Object]
     pub fn new_object() -> Self {
         Self(Object::new().into())
@@ -207,6 +605,12 @@ impl Reflection {
         Ok(self)
     }
 
+    /// Check if the `key` exists in the value of [Self](Reflection).
+    /// Returns `false` if the key is missing or if the value is not a JS object
+    pub fn has(&self, key: &str) -> bool {
+        self.0.is_object() && Reflect::has(&self.0, &key.into()).unwrap_or_default()
+    }
+
     /// Reflect the `key` from the value of [Self](Reflection) and return the
     /// reflected value
     pub fn reflect_inner(&self, key: &str) -> WalletResult<JsValue> {
@@ -217,6 +621,19 @@ impl Reflection {
         Ok(inner)
     }
 
+    /// Reflect the `key` from the value of [Self](Reflection) for optional fields.
+    /// Returns `Ok(None)` if the reflected value is null or undefined
+    /// and only returns an error if the reflection itself fails
+    pub fn get_optional(&self, key: &str) -> WalletResult<Option<Reflection>> {
+        let inner = Reflect::get(&self.0, &key.into())?;
+
+        if inner.is_undefined() || inner.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(Self(inner)))
+        }
+    }
+
     /// Reflect the `key` from the value of [Self](Reflection) and return the
     /// reflected value as a [String]
     pub fn string(&self, key: &str) -> WalletResult<String> {
@@ -272,8 +689,11 @@ impl Reflection {
     /// reflected value as a 32 byte array
     pub fn byte32array(&self, key: &str) -> WalletResult<[u8; 32]> {
         let js_value = Reflect::get(&self.0, &key.into())?;
+        let js_typeof = Self::js_typeof(&js_value);
 
-        let to_js_array: js_sys::Uint8Array = js_value.unchecked_into();
+        let to_js_array = js_value
+            .dyn_into::<js_sys::Uint8Array>()
+            .or(Err(Self::concat_error("Uint8Array", &js_typeof)))?;
 
         let byte32array: [u8; 32] = to_js_array
             .to_vec()
@@ -283,6 +703,25 @@ impl Reflection {
         Ok(byte32array)
     }
 
+    /// Reflect the `key` from the value of [Self](Reflection) as an array of byte arrays
+    /// and parse each element as an Ed25519 public key using [Utils::public_key].
+    /// Returns [WalletError::InvalidPublicKeyAtIndex] with the index of the first element
+    /// that is not 32 bytes or not a valid public key
+    pub fn public_keys(&self, key: &str) -> WalletResult<Vec<VerifyingKey>> {
+        self.reflect_js_array(key)?
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                value
+                    .dyn_into::<js_sys::Uint8Array>()
+                    .ok()
+                    .and_then(|bytes| Utils::to32byte_array(&bytes.to_vec()).ok())
+                    .and_then(|bytes| Utils::public_key(bytes).ok())
+                    .ok_or(WalletError::InvalidPublicKeyAtIndex(index))
+            })
+            .collect()
+    }
+
     /// Return the value of [Self](Reflection) as a [js_sys::Array]
     /// without consuming `Self`
     pub fn get_array(&self) -> WalletResult<Array> {
@@ -346,22 +785,40 @@ impl Reflection {
     pub(crate) fn object_to_vec_string(&self, key: &str) -> WalletResult<Vec<String>> {
         let features_value = self.reflect_inner(key)?;
 
-        let js_typeof = Self::js_typeof(&self.0);
+        Reflection::new(features_value)?.keys()
+    }
 
-        let features_object = features_value
+    /// Borrow the value of [Self](Reflection) as an [Object].
+    /// An error is returned if the value is not a JS object
+    pub fn as_object(&self) -> WalletResult<&Object> {
+        self.0
             .dyn_ref::<Object>()
-            .ok_or(Self::concat_error("JS Object", &js_typeof))?;
+            .ok_or_else(|| Self::concat_error("JS Object", &Self::js_typeof(&self.0)))
+    }
 
-        Object::keys(features_object)
+    /// Get the keys of the value of [Self](Reflection) using `Object.keys()`.
+    /// An error is returned if the value is not a JS object
+    pub fn keys(&self) -> WalletResult<Vec<String>> {
+        Object::keys(self.as_object()?)
             .iter()
-            .map(|value| {
-                value.as_string().ok_or(WalletError::InternalError(format!(
-                    "{value:?} is not a JsString"
-                )))
-            })
+            .map(|value| Self::get_string(&value))
             .collect::<WalletResult<Vec<String>>>()
     }
 
+    /// Get the key and value pairs of the value of [Self](Reflection) using `Object.entries()`
+    /// with each value wrapped in a [Reflection].
+    /// An error is returned if the value is not a JS object
+    pub fn entries(&self) -> WalletResult<Vec<(String, Reflection)>> {
+        Object::entries(self.as_object()?)
+            .iter()
+            .map(|entry| {
+                let entry = entry.unchecked_into::<Array>();
+
+                Ok((Self::get_string(&entry.get(0))?, Self(entry.get(1))))
+            })
+            .collect()
+    }
+
     /// Check if [Self](Reflection) is null or undefined
     pub fn check_is_undefined(value: &JsValue) -> WalletResult<()> {
         if value.is_undefined() || value.is_null() {
@@ -382,6 +839,37 @@ impl Reflection {
         js_value.dyn_into::<Function>().or(incase_of_error)
     }
 
+    /// Reflect the function at `key`, call it with the value of [Self](Reflection) as `this`
+    /// and no arguments and wrap the result in [Reflection].
+    /// Use [Self::call_await] for functions returning a Promise
+    pub fn call0(&self, key: &str) -> WalletResult<Reflection> {
+        let outcome = self.get_function(key)?.call0(&self.0)?;
+
+        Reflection::new(outcome)
+    }
+
+    /// Same as [Self::call0] but calls the function with the `arg` argument
+    pub fn call1(&self, key: &str, arg: &JsValue) -> WalletResult<Reflection> {
+        let outcome = self.get_function(key)?.call1(&self.0, arg)?;
+
+        Reflection::new(outcome)
+    }
+
+    /// Call the `func` with `this` and the `args` and if the result is a Promise
+    /// await it. A rejected Promise is returned as a [WalletError]
+    pub async fn call_await(
+        func: &Function,
+        this: &JsValue,
+        args: &Array,
+    ) -> WalletResult<JsValue> {
+        let outcome = func.apply(this, args)?;
+
+        match outcome.dyn_into::<js_sys::Promise>() {
+            Ok(promise) => Ok(wasm_bindgen_futures::JsFuture::from(promise).await?),
+            Err(value) => Ok(value),
+        }
+    }
+
     /// Get the value of [Self](Reflection) without consuming Self
     pub fn get_inner(&self) -> &JsValue {
         &self.0
@@ -391,7 +879,10 @@ impl Reflection {
     pub fn js_typeof(value: &JsValue) -> String {
         // https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/typeof
         // The `typeof` in Js should always be a string
-        value.js_typeof().as_string().unwrap_or_else(|| "unknown".to_string())
+        value
+            .js_typeof()
+            .as_string()
+            .unwrap_or_else(|| "unknown".to_string())
     }
 
     /// Consume [Self](Reflection) and return it's value as a [Function]
@@ -412,6 +903,51 @@ impl Reflection {
             .or(Err(Self::concat_error("Array", &js_typeof)))
     }
 
+    /// The maximum depth [Self::walk] descends into nested objects
+    pub const MAX_WALK_DEPTH: usize = 16;
+
+    /// Recursively visit every scalar leaf of the value of [Self](Reflection),
+    /// calling the `visitor` with the path of keys leading to the leaf and the leaf value.
+    /// Byte arrays (`Uint8Array`) and functions are visited as leaves.
+    /// Traversal stops descending after a depth of [Self::MAX_WALK_DEPTH]
+    /// to avoid cycles in the object graph.
+    pub fn walk(&self, visitor: &mut impl FnMut(&[&str], &JsValue)) -> WalletResult<()> {
+        let mut path = Vec::<String>::new();
+
+        Self::walk_inner(&self.0, &mut path, visitor)
+    }
+
+    fn walk_inner(
+        value: &JsValue,
+        path: &mut Vec<String>,
+        visitor: &mut impl FnMut(&[&str], &JsValue),
+    ) -> WalletResult<()> {
+        let is_leaf = !value.is_object()
+            || value.is_function()
+            || value.is_instance_of::<js_sys::Uint8Array>()
+            || path.len() >= Self::MAX_WALK_DEPTH;
+
+        if is_leaf {
+            let path_str = path.iter().map(String::as_str).collect::<Vec<&str>>();
+            visitor(&path_str, value);
+
+            return Ok(());
+        }
+
+        let object = value.unchecked_ref::<Object>();
+
+        Object::keys(object).iter().try_for_each(|key| {
+            let key_str = Self::get_string(&key)?;
+            let inner = Reflect::get(object, &key)?;
+
+            path.push(key_str);
+            let outcome = Self::walk_inner(&inner, path, visitor);
+            path.pop();
+
+            outcome
+        })
+    }
+
     fn concat_error(expected: &str, encountered: &str) -> WalletError {
         WalletError::InternalError(
             String::new()
@@ -424,14 +960,606 @@ impl Reflection {
     }
 }
 
+#[cfg(feature = "browser")]
 impl Default for Reflection {
     fn default() -> Self {
         Reflection(JsValue::undefined())
     }
 }
 
+#[cfg(feature = "browser")]
 impl Clone for Reflection {
     fn clone(&self) -> Self {
         Reflection(self.0.clone())
     }
 }
+
+#[cfg(test)]
+mod utils_tests {
+    use super::*;
+
+    #[test]
+    fn explorer_urls() {
+        let signature = Signature::from_bytes(&[1u8; 64]);
+        let public_key = Utils::public_key([3u8; 32]).unwrap();
+        let address = Utils::address(public_key);
+        let base58_signature = Utils::base58_signature(signature);
+
+        assert_eq!(
+            Utils::explorer_tx_url(&signature, &Cluster::MainNet),
+            format!("https://explorer.solana.com/tx/{base58_signature}")
+        );
+        assert_eq!(
+            Utils::explorer_tx_url(&signature, &Cluster::DevNet),
+            format!("https://explorer.solana.com/tx/{base58_signature}?cluster=devnet")
+        );
+        assert_eq!(
+            Utils::explorer_address_url(&public_key, &Cluster::TestNet),
+            format!("https://explorer.solana.com/address/{address}?cluster=testnet")
+        );
+        assert_eq!(
+            Utils::explorer_address_url(&public_key, &Cluster::LocalNet),
+            format!(
+                "https://explorer.solana.com/address/{address}?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+            )
+        );
+    }
+
+    #[test]
+    fn canonical_feature_name() {
+        assert_eq!(
+            Utils::canonical_feature_name("solana:signMessage"),
+            Some(SOLANA_SIGN_MESSAGE_IDENTIFIER)
+        );
+        assert_eq!(
+            Utils::canonical_feature_name("solana:sign-message"),
+            Some(SOLANA_SIGN_MESSAGE_IDENTIFIER)
+        );
+        assert_eq!(
+            Utils::canonical_feature_name("Solana:Sign_And_Send_Transaction"),
+            Some(SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER)
+        );
+        assert_eq!(
+            Utils::canonical_feature_name("STANDARD:CONNECT"),
+            Some(STANDARD_CONNECT_IDENTIFIER)
+        );
+        assert_eq!(Utils::canonical_feature_name("solana:signFoo"), None);
+        assert_eq!(Utils::canonical_feature_name("phantom:connect"), None);
+    }
+
+    #[test]
+    fn base64_encoding() {
+        assert_eq!(Utils::base64_encode(b"wallet"), "d2FsbGV0");
+        assert_eq!(Utils::base64_decode("d2FsbGV0"), Ok(b"wallet".to_vec()));
+        assert_eq!(
+            Utils::base64_decode(&Utils::base64_encode(&[0, 15, 255])),
+            Ok(vec![0, 15, 255])
+        );
+        assert!(matches!(
+            Utils::base64_decode("d2FsbGV0!"),
+            Err(WalletError::Base64Decode(_))
+        ));
+    }
+
+    #[test]
+    fn hex_encoding() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let signature = signing_key.sign(b"hex");
+
+        let public_key_hex = Utils::public_key_to_hex(&public_key);
+        assert_eq!(public_key_hex.len(), 64);
+        assert_eq!(Utils::public_key_from_hex(&public_key_hex), Ok(public_key));
+        assert_eq!(
+            Utils::public_key_from_hex(&public_key_hex.to_uppercase()),
+            Ok(public_key)
+        );
+
+        let signature_hex = Utils::signature_to_hex(&signature);
+        assert_eq!(signature_hex.len(), 128);
+        assert_eq!(Utils::signature_from_hex(&signature_hex), Ok(signature));
+
+        assert_eq!(Utils::hex_encode(&[0, 15, 255]), "000fff");
+        assert_eq!(
+            Utils::public_key_from_hex(&signature_hex),
+            Err(WalletError::Expected32ByteLength)
+        );
+        assert_eq!(
+            Utils::signature_from_hex(&public_key_hex),
+            Err(WalletError::Expected64ByteLength)
+        );
+        assert!(Utils::hex_decode("abc").is_err());
+        assert!(Utils::hex_decode("zz").is_err());
+    }
+
+    #[test]
+    fn verify_signature_bytes() {
+        let (signing_key, public_key) = Utils::keypair_rand();
+        let signature = Utils::sign_message(&signing_key, b"bytes").to_bytes();
+
+        assert!(Utils::verify_signature_bytes(public_key.as_bytes(), b"bytes", &signature).is_ok());
+        assert_eq!(
+            Utils::verify_signature_bytes(public_key.as_bytes(), b"other", &signature),
+            Err(WalletError::InvalidSignature)
+        );
+        assert_eq!(
+            Utils::verify_signature_bytes(&public_key.as_bytes()[1..], b"bytes", &signature),
+            Err(WalletError::Expected32ByteLength)
+        );
+        assert_eq!(
+            Utils::verify_signature_bytes(public_key.as_bytes(), b"bytes", &signature[1..]),
+            Err(WalletError::Expected64ByteLength)
+        );
+    }
+
+    #[test]
+    fn rand_32bytes_with() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        assert_eq!(
+            Utils::rand_32bytes_with(&mut ChaCha20Rng::seed_from_u64(7)),
+            Utils::rand_32bytes_with(&mut ChaCha20Rng::seed_from_u64(7))
+        );
+        assert_ne!(
+            Utils::rand_32bytes_with(&mut ChaCha20Rng::seed_from_u64(7)),
+            Utils::rand_32bytes_with(&mut ChaCha20Rng::seed_from_u64(8))
+        );
+    }
+
+    #[test]
+    fn keypair_rand() {
+        use ed25519_dalek::Signer;
+
+        let (signing_key, public_key) = Utils::keypair_rand();
+        let signature = Utils::sign_message(&signing_key, b"keypair");
+        assert_eq!(signature, signing_key.sign(b"keypair"));
+
+        assert!(Utils::verify_signature(public_key, b"keypair", signature).is_ok());
+        assert_eq!(
+            Utils::verify_signature(public_key, b"another", signature),
+            Err(WalletError::InvalidSignature)
+        );
+        assert_ne!(Utils::keypair_rand().1, public_key);
+    }
+
+    #[test]
+    fn verify_message_base58() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let message = b"Sign In With Solana";
+        let address = Utils::address(signing_key.verifying_key());
+        let signature = Utils::base58_signature(signing_key.sign(message));
+
+        assert!(Utils::verify_message_base58(&address, message, &signature).is_ok());
+        assert_eq!(
+            Utils::verify_message_base58(&address, b"Another message", &signature),
+            Err(WalletError::InvalidSignature)
+        );
+        assert_eq!(
+            Utils::verify_message_base58("0OIl", message, &signature),
+            Err(WalletError::InvalidBase58Address)
+        );
+        assert_eq!(
+            Utils::verify_message_base58(&address, message, "0OIl"),
+            Err(WalletError::Base58Decode(
+                "provided string contained invalid character '0' at byte 0".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn is_valid_base58_address() {
+        let address =
+            Utils::address(ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]).verifying_key());
+
+        assert!(Utils::is_valid_base58_address(&address));
+        assert!(!Utils::is_valid_base58_address(&address[..20]));
+        assert!(!Utils::is_valid_base58_address(
+            &bs58::encode([1u8; 33]).into_string()
+        ));
+        assert!(!Utils::is_valid_base58_address("0OIl"));
+        assert!(!Utils::is_valid_base58_address(""));
+    }
+
+    #[test]
+    fn message_preview() {
+        assert_eq!(Utils::message_preview(b"Sign in", 10), "Sign in");
+        assert_eq!(Utils::message_preview(b"Sign in", 4), "Sign...");
+        assert_eq!(Utils::message_preview("héllo".as_bytes(), 2), "hé...");
+        assert_eq!(Utils::message_preview(&[0xff, 0x00, 0x10], 2), "ff00...");
+        assert_eq!(Utils::message_preview(&[0xff, 0x00], 2), "ff00");
+    }
+
+    #[test]
+    fn shorten_base58() {
+        assert_eq!(
+            Utils::shorten_base58("FXdl1234RGd4").unwrap(),
+            "FXdl...RGd4"
+        );
+        assert_eq!(
+            Utils::shorten_base58("FXdl"),
+            Err(WalletError::InvalidBase58Address)
+        );
+        assert_eq!(
+            Utils::shorten_base58("ééééé"),
+            Err(WalletError::InvalidBase58Address)
+        );
+        assert_eq!(
+            Utils::custom_shorten_base58("FXdl1234RGd4", usize::MAX),
+            Err(WalletError::InvalidBase58Address)
+        );
+
+        assert_eq!(
+            Utils::shorten_base58_asymmetric("FXdlWq12RGd4", 6, 4).unwrap(),
+            "FXdlWq...RGd4"
+        );
+        assert_eq!(
+            Utils::shorten_base58_asymmetric("FXdlWq12RGd4", 8, 4).unwrap(),
+            "FXdlWq12...RGd4"
+        );
+        assert_eq!(
+            Utils::shorten_base58_asymmetric("FXdlWq12RGd4", 9, 4),
+            Err(WalletError::InvalidBase58Address)
+        );
+    }
+
+    #[test]
+    fn validate_and_shorten() {
+        let address =
+            Utils::address(ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]).verifying_key());
+
+        assert_eq!(
+            Utils::validate_and_shorten(&address, 4).unwrap(),
+            String::new() + &address[..4] + "..." + &address[address.len() - 4..]
+        );
+        assert_eq!(
+            Utils::validate_and_shorten(&address[..20], 4),
+            Err(WalletError::Expected32ByteLength)
+        );
+
+        // `y = 2` is not the y-coordinate of a point on the Ed25519 curve
+        let mut off_curve = [0u8; 32];
+        off_curve[0] = 2;
+        assert_eq!(
+            Utils::validate_and_shorten(&bs58::encode(off_curve).into_string(), 4),
+            Err(WalletError::InvalidEd25519PublicKeyBytes)
+        );
+    }
+
+    #[test]
+    fn verify_message_lenient() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = signing_key.verifying_key();
+
+        let raw_signature = signing_key.sign("Line one\r\nLine two".as_bytes());
+        assert!(
+            Utils::verify_message_lenient(public_key, "Line one\r\nLine two", raw_signature)
+                .is_ok()
+        );
+
+        let normalized_signature = signing_key.sign("Line one\nLine two".as_bytes());
+        assert!(Utils::verify_message_lenient(
+            public_key,
+            "Line one\r\nLine two",
+            normalized_signature
+        )
+        .is_ok());
+        assert!(Utils::verify_message_lenient(
+            public_key,
+            "Line one\rLine two",
+            normalized_signature
+        )
+        .is_ok());
+
+        assert_eq!(
+            Utils::verify_message_lenient(public_key, "Line one Line two", normalized_signature),
+            Err(WalletError::InvalidSignature)
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "browser", target_arch = "wasm32"))]
+mod reflection_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn walk() {
+        let mut inner = Reflection::new_object();
+        inner.set_object_str("address", "FooBar").unwrap();
+        inner
+            .set_object(
+                &"publicKey".into(),
+                &js_sys::Uint8Array::from([1u8; 32].as_slice()),
+            )
+            .unwrap();
+
+        let mut outer = Reflection::new_object();
+        outer.set_object_str("name", "Foo").unwrap();
+        outer
+            .set_object(&"account".into(), inner.get_inner())
+            .unwrap();
+
+        let mut visited = Vec::<String>::new();
+        outer
+            .walk(&mut |path, _value| visited.push(path.join(".")))
+            .unwrap();
+
+        assert_eq!(
+            visited,
+            vec![
+                "name".to_string(),
+                "account.address".to_string(),
+                "account.publicKey".to_string()
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn new_from_path() {
+        let mut inner = Reflection::new_object();
+        inner.set_object_str("version", "1.0.0").unwrap();
+
+        let mut features = Reflection::new_object();
+        features
+            .set_object(&"solana:signMessage".into(), inner.get_inner())
+            .unwrap();
+
+        let reflection =
+            Reflection::new_from_path(features.get_inner(), &["solana:signMessage", "version"])
+                .unwrap();
+        assert_eq!(reflection.get_inner().as_string().as_deref(), Some("1.0.0"));
+
+        assert_eq!(
+            Reflection::new_from_path(features.get_inner(), &["solana:signMessage", "signMessage"])
+                .err(),
+            Some(WalletError::ExpectedValueNotFound(
+                "signMessage".to_string()
+            ))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn call() {
+        let object = js_sys::Function::new_with_args(
+            "",
+            "return { name: 'Foo', greet(value) { return this.name + value; } }",
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let reflection = Reflection::new(object).unwrap();
+
+        assert_eq!(
+            reflection
+                .call1("greet", &"Bar".into())
+                .unwrap()
+                .get_inner()
+                .as_string()
+                .as_deref(),
+            Some("FooBar")
+        );
+        assert_eq!(
+            reflection
+                .call0("greet")
+                .unwrap()
+                .get_inner()
+                .as_string()
+                .as_deref(),
+            Some("Fooundefined")
+        );
+        assert!(reflection.call0("name").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn call_await() {
+        use std::{
+            future::Future,
+            pin::pin,
+            task::{Context, Poll},
+        };
+
+        use crate::events::event_filter_tests::noop_waker;
+
+        let function = js_sys::Function::new_with_args("value", "return value");
+        let this = JsValue::null();
+        let args = Array::of1(&"Foo".into());
+        let mut future = pin!(Reflection::call_await(&function, &this, &args));
+
+        // A value that is not a Promise is returned without yielding to the JS event loop
+        let outcome = future
+            .as_mut()
+            .poll(&mut Context::from_waker(&noop_waker()));
+        assert!(
+            matches!(outcome, Poll::Ready(Ok(value)) if value.as_string().as_deref() == Some("Foo"))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_sign_message_response() {
+        let (signing_key, public_key) = Utils::keypair_rand();
+        let signature = Utils::sign_message(&signing_key, b"Hello");
+
+        let mut account = Reflection::new_object();
+        account
+            .set_object(
+                &"publicKey".into(),
+                &Utils::to_uint8array(public_key.as_bytes()),
+            )
+            .unwrap();
+
+        let mut response = Reflection::new_object();
+        response
+            .set_object(&"signedMessage".into(), &Utils::to_uint8array(b"Hello"))
+            .unwrap();
+        response
+            .set_object(
+                &"signature".into(),
+                &Utils::to_uint8array(&signature.to_bytes()),
+            )
+            .unwrap();
+        response
+            .set_object(&"account".into(), account.get_inner())
+            .unwrap();
+
+        let signed_message = Utils::parse_sign_message_response(response.take()).unwrap();
+        assert_eq!(signed_message.message(), b"Hello");
+        assert_eq!(signed_message.public_key(), public_key);
+
+        let mut tampered = Reflection::new_object();
+        tampered
+            .set_object(&"signedMessage".into(), &Utils::to_uint8array(b"Bye"))
+            .unwrap();
+        tampered
+            .set_object(
+                &"signature".into(),
+                &Utils::to_uint8array(&signature.to_bytes()),
+            )
+            .unwrap();
+        tampered
+            .set_object(
+                &"publicKey".into(),
+                &Utils::to_uint8array(public_key.as_bytes()),
+            )
+            .unwrap();
+        assert_eq!(
+            Utils::parse_sign_message_response(tampered.take()),
+            Err(WalletError::InvalidSignature)
+        );
+
+        let mut without_signer = Reflection::new_object();
+        without_signer
+            .set_object(&"signedMessage".into(), &Utils::to_uint8array(b"Hello"))
+            .unwrap();
+        without_signer
+            .set_object(
+                &"signature".into(),
+                &Utils::to_uint8array(&signature.to_bytes()),
+            )
+            .unwrap();
+        let without_signer = without_signer.take();
+        assert!(Utils::parse_sign_message_response(without_signer.clone()).is_err());
+        assert_eq!(
+            Utils::parse_sign_message_item(without_signer, Some(public_key.to_bytes()))
+                .map(|signed_message| signed_message.public_key()),
+            Ok(public_key)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn byte32array() {
+        let mut reflection = Reflection::new_object();
+        reflection
+            .set_object(
+                &"publicKey".into(),
+                &js_sys::Uint8Array::from([1u8; 32].as_slice()),
+            )
+            .unwrap();
+        reflection.set_object_str("address", "FooBar").unwrap();
+
+        assert_eq!(reflection.byte32array("publicKey"), Ok([1u8; 32]));
+        assert!(matches!(
+            reflection.byte32array("address"),
+            Err(WalletError::InternalError(error)) if error.contains("string")
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn public_keys() {
+        let (_, public_key) = Utils::keypair_rand();
+
+        let keys = Array::new();
+        keys.push(&js_sys::Uint8Array::from(public_key.as_bytes().as_slice()));
+
+        let mut reflection = Reflection::new_object();
+        reflection.set_object(&"keys".into(), &keys).unwrap();
+        assert_eq!(reflection.public_keys("keys"), Ok(vec![public_key]));
+
+        keys.push(&js_sys::Uint8Array::from([1u8; 31].as_slice()));
+        assert_eq!(
+            reflection.public_keys("keys"),
+            Err(WalletError::InvalidPublicKeyAtIndex(1))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn get_optional() {
+        let mut reflection = Reflection::new_object();
+        reflection.set_object_str("label", "FooBar").unwrap();
+        reflection
+            .set_object(&"icon".into(), &JsValue::null())
+            .unwrap();
+
+        assert_eq!(
+            reflection
+                .get_optional("label")
+                .unwrap()
+                .map(|label| label.get_inner().as_string()),
+            Some(Some("FooBar".to_string()))
+        );
+        assert!(reflection.get_optional("icon").unwrap().is_none());
+        assert!(reflection.get_optional("features").unwrap().is_none());
+        assert!(Reflection(JsValue::undefined())
+            .get_optional("label")
+            .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn uint8array_round_trip() {
+        let bytes = [0u8, 1, 127, 128, 255];
+        let array = Utils::to_uint8array(&bytes);
+
+        assert_eq!(array.length(), 5);
+        assert_eq!(Utils::from_uint8array(&array), bytes.to_vec());
+        assert!(Utils::from_uint8array(&Utils::to_uint8array(&[])).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn has() {
+        let mut reflection = Reflection::new_object();
+        reflection.set_object_str("account", "FooBar").unwrap();
+
+        assert!(reflection.has("account"));
+        assert!(!reflection.has("label"));
+        assert!(!Reflection::new("Foo".into()).unwrap().has("length"));
+    }
+
+    #[wasm_bindgen_test]
+    fn keys() {
+        let mut reflection = Reflection::new_object();
+        reflection.set_object_str("name", "Foo").unwrap();
+        reflection.set_object_str("version", "1.0.0").unwrap();
+
+        assert_eq!(
+            reflection.keys().unwrap(),
+            vec!["name".to_string(), "version".to_string()]
+        );
+        assert!(Reflection::new("Foo".into()).unwrap().keys().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn as_object() {
+        let reflection = Reflection::new_object();
+
+        assert!(reflection.as_object().is_ok());
+        assert!(Reflection::new("Foo".into()).unwrap().as_object().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn entries() {
+        let mut reflection = Reflection::new_object();
+        reflection.set_object_str("name", "Foo").unwrap();
+
+        let entries = reflection.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, "name");
+        assert_eq!(entries[0].1.get_inner(), &JsValue::from_str("Foo"));
+        assert!(Reflection::new("Foo".into()).unwrap().entries().is_err());
+    }
+}
@@ -86,6 +86,17 @@ impl Cluster {
         }
     }
 
+    /// The canonical public RPC endpoint of the cluster.
+    /// Returns `None` for [Cluster::LocalNet] since a local validator is not a public endpoint
+    pub fn default_rpc_url(&self) -> Option<&'static str> {
+        match self {
+            Cluster::MainNet => Some(MAINNET_ENDPOINT),
+            Cluster::DevNet => Some(DEVNET_ENDPOINT),
+            Cluster::TestNet => Some(TESTNET_ENDPOINT),
+            Cluster::LocalNet => None,
+        }
+    }
+
     /// A Solana cluster identifier
     pub fn chain(&self) -> &str {
         match self {
@@ -207,4 +218,21 @@ mod chain_tests {
         );
         assert_eq!(Cluster::LocalNet.endpoint(), "http://localhost:8899");
     }
+
+    #[test]
+    fn default_rpc_url() {
+        assert_eq!(
+            Cluster::MainNet.default_rpc_url(),
+            Some("https://api.mainnet-beta.solana.com")
+        );
+        assert_eq!(
+            Cluster::DevNet.default_rpc_url(),
+            Some("https://api.devnet.solana.com")
+        );
+        assert_eq!(
+            Cluster::TestNet.default_rpc_url(),
+            Some("https://api.testnet.solana.com")
+        );
+        assert_eq!(Cluster::LocalNet.default_rpc_url(), None);
+    }
 }
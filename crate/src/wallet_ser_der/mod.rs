@@ -1,20 +1,30 @@
 mod version;
 pub use version::*;
 
+#[cfg(feature = "browser")]
 mod wallet_icon;
+#[cfg(feature = "browser")]
 pub use wallet_icon::*;
 
 mod chains;
 pub use chains::*;
 
+#[cfg(feature = "browser")]
 mod wallet;
+#[cfg(feature = "browser")]
 pub use wallet::*;
 
+#[cfg(feature = "browser")]
 mod wallet_account;
+#[cfg(feature = "browser")]
 pub use wallet_account::*;
 
+#[cfg(feature = "browser")]
 mod standard_features;
+#[cfg(feature = "browser")]
 pub use standard_features::*;
 
+#[cfg(feature = "browser")]
 mod signin_standard;
+#[cfg(feature = "browser")]
 pub use signin_standard::*;
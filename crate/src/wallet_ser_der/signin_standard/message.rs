@@ -0,0 +1,192 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{SigninInput, Utils};
+
+/// A Sign In With Solana (SIWS) message constructed deterministically
+/// in the order defined by the ABNF of the
+/// [SIWS](https://github.com/phantom/sign-in-with-solana) standard.
+/// The bytes returned by [SignInMessage::to_bytes] are the bytes signed by the wallet
+/// and can be passed directly to [crate::Utils::verify_signature].
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignInMessage {
+    domain: String,
+    address: String,
+    statement: Option<String>,
+    nonce: Option<String>,
+    issued_at: Option<SystemTime>,
+}
+
+impl SignInMessage {
+    /// Initialize the message with the EIP-4361 `domain` requesting the sign-in
+    /// and the Base58 `address` performing the sign-in
+    pub fn new(domain: &str, address: &str) -> Self {
+        Self {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// An EIP-4361 Statement which is a human readable string and should not have new-line characters (\n).
+    pub fn set_statement(&mut self, statement: &str) -> &mut Self {
+        self.statement.replace(statement.to_string());
+
+        self
+    }
+
+    /// An EIP-4361 Nonce which is an alphanumeric string containing a minimum of 8 characters.
+    pub fn set_nonce(&mut self, nonce: &str) -> &mut Self {
+        self.nonce.replace(nonce.to_string());
+
+        self
+    }
+
+    /// The time at which the sign-in request was issued to the wallet.
+    /// This is formatted as an ISO 8601 datetime string
+    pub fn set_issued_at(&mut self, issued_at: SystemTime) -> &mut Self {
+        self.issued_at.replace(issued_at);
+
+        self
+    }
+
+    /// Get the `domain` field
+    pub fn domain(&self) -> &str {
+        self.domain.as_str()
+    }
+
+    /// Get the `address` field
+    pub fn address(&self) -> &str {
+        self.address.as_str()
+    }
+
+    /// Get the `statement` field
+    pub fn statement(&self) -> Option<&String> {
+        self.statement.as_ref()
+    }
+
+    /// Get the `nonce` field
+    pub fn nonce(&self) -> Option<&String> {
+        self.nonce.as_ref()
+    }
+
+    /// Get the `issued_at` field
+    pub fn issued_at(&self) -> Option<&SystemTime> {
+        self.issued_at.as_ref()
+    }
+
+    /// Check whether the message was issued at most `max_age_millis` milliseconds ago
+    /// using [Utils::now_millis] as the current time, so a stale signed message can be rejected.
+    /// A message without an `issued_at` time or issued in the future is not fresh.
+    /// Requires the `wasm32` target
+    pub fn is_fresh(&self, max_age_millis: u64) -> bool {
+        self.is_fresh_at(max_age_millis, Utils::now_millis())
+    }
+
+    fn is_fresh_at(&self, max_age_millis: u64, now_millis: f64) -> bool {
+        self.issued_at
+            .and_then(|issued_at| issued_at.duration_since(UNIX_EPOCH).ok())
+            .map(|issued_at| now_millis - issued_at.as_millis() as f64)
+            .is_some_and(|age| (0f64..=max_age_millis as f64).contains(&age))
+    }
+
+    /// Construct the UTF-8 message in the canonical ABNF order
+    pub fn to_message(&self) -> String {
+        let mut message = String::new()
+            + self.domain.as_str()
+            + " wants you to sign in with your Solana account:\n"
+            + self.address.as_str();
+
+        if let Some(statement) = self.statement.as_ref() {
+            message = message + "\n\n" + statement;
+        }
+
+        let mut advanced_fields = Vec::<String>::new();
+
+        if let Some(nonce) = self.nonce.as_ref() {
+            advanced_fields.push(String::new() + "Nonce: " + nonce);
+        }
+
+        if let Some(issued_at) = self.issued_at {
+            advanced_fields.push(
+                String::new() + "Issued At: " + &SigninInput::to_iso860(issued_at).to_string(),
+            );
+        }
+
+        if !advanced_fields.is_empty() {
+            message = message + "\n\n" + &advanced_fields.join("\n");
+        }
+
+        message
+    }
+
+    /// Construct the message bytes in the canonical ABNF order
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_message().into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod signin_message_tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+    use crate::Utils;
+
+    #[test]
+    fn to_bytes() {
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        let address = Utils::address(signing_key.verifying_key());
+
+        let mut message = SignInMessage::new("example.com", &address);
+        assert_eq!(
+            message.to_message(),
+            String::new()
+                + "example.com wants you to sign in with your Solana account:\n"
+                + &address
+        );
+
+        message
+            .set_statement("Sign in to Example")
+            .set_nonce("abcd1234")
+            .set_issued_at(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+
+        let expected = String::new()
+            + "example.com wants you to sign in with your Solana account:\n"
+            + &address
+            + "\n\nSign in to Example\n\nNonce: abcd1234\nIssued At: 2023-11-14T22:13:20.000Z";
+        assert_eq!(message.to_message(), expected);
+
+        let parsed = SigninInput::parser(&message.to_message()).unwrap();
+        assert_eq!(parsed.domain().map(String::as_str), Some("example.com"));
+        assert_eq!(parsed.address(), Some(&address));
+        assert_eq!(
+            parsed.statement().map(String::as_str),
+            Some("Sign in to Example")
+        );
+        assert_eq!(parsed.nonce().map(String::as_str), Some("abcd1234"));
+        assert_eq!(parsed.issued_at(), message.issued_at());
+
+        let message_bytes = message.to_bytes();
+        let signature = signing_key.sign(&message_bytes);
+        assert!(
+            Utils::verify_signature(signing_key.verifying_key(), &message_bytes, signature).is_ok()
+        );
+    }
+
+    #[test]
+    fn is_fresh_at() {
+        let mut message = SignInMessage::new("example.com", "address");
+        let issued_at_millis = 1_700_000_000_000f64;
+
+        assert!(!message.is_fresh_at(60_000, issued_at_millis));
+
+        message.set_issued_at(UNIX_EPOCH + Duration::from_millis(issued_at_millis as u64));
+
+        assert!(message.is_fresh_at(60_000, issued_at_millis));
+        assert!(message.is_fresh_at(60_000, issued_at_millis + 60_000f64));
+        assert!(!message.is_fresh_at(60_000, issued_at_millis + 60_001f64));
+        assert!(!message.is_fresh_at(60_000, issued_at_millis - 1f64));
+    }
+}
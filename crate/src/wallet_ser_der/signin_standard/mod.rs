@@ -3,3 +3,6 @@ pub use input::*;
 
 mod output;
 pub use output::*;
+
+mod message;
+pub use message::*;
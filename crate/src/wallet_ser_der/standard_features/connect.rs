@@ -17,10 +17,38 @@ impl Connect {
         )?))
     }
 
-    /// Connect to a wallet by calling the callback function
-    pub(crate) async fn call_connect(&self) -> WalletResult<WalletAccount> {
+    /// Connect to a wallet by calling the callback function.
+    /// Returns all the accounts returned by the wallet, which are never empty
+    pub(crate) async fn call_connect(&self) -> WalletResult<Vec<WalletAccount>> {
         let outcome = self.0.callback.call0(&JsValue::from_bool(false))?;
 
+        Self::parse_connect_outcome(outcome, WalletError::connect_error).await
+    }
+
+    /// Connect to a wallet by calling the callback function with the `silent` input
+    /// set to `true`. The wallet only returns the accounts the app is already authorized
+    /// to use without prompting the user.
+    /// Returns [WalletError::SilentConnectUnsupported] if the callback throws synchronously
+    /// or rejects with a `TypeError`
+    pub(crate) async fn call_connect_silent(&self) -> WalletResult<Vec<WalletAccount>> {
+        let mut input = Reflection::new_object();
+        input.set_object(&"silent".into(), &JsValue::from_bool(true))?;
+
+        let outcome = self
+            .0
+            .callback
+            .call1(&JsValue::null(), input.get_inner())
+            .map_err(|error| {
+                WalletError::SilentConnectUnsupported(WalletError::from(error).to_string())
+            })?;
+
+        Self::parse_connect_outcome(outcome, WalletError::silent_connect_error).await
+    }
+
+    async fn parse_connect_outcome(
+        outcome: JsValue,
+        connect_error: fn(JsValue) -> WalletError,
+    ) -> WalletResult<Vec<WalletAccount>> {
         let outcome = js_sys::Promise::resolve(&outcome);
 
         wasm_bindgen_futures::JsFuture::from(outcome)
@@ -28,24 +56,22 @@ impl Connect {
             .map(|success| {
                 let get_accounts = Reflection::new(success)?.reflect_js_array("accounts")?;
 
-                let wallet_account = get_accounts
+                let wallet_accounts = get_accounts
                     .into_iter()
                     .map(|raw_account| WalletAccount::parse(Reflection::new(raw_account)?))
-                    .collect::<WalletResult<Vec<WalletAccount>>>()
-                    .map(|mut accounts| {
-                        if accounts.is_empty() {
-                            Err(WalletError::ConnectHasNoAccounts)
-                        } else {
-                            Ok(accounts.remove(0))
-                        }
-                    })??;
-
-                Ok(wallet_account)
+                    .collect::<WalletResult<Vec<WalletAccount>>>()?;
+
+                if wallet_accounts.is_empty() {
+                    Err(WalletError::ConnectHasNoAccounts)
+                } else {
+                    Ok(wallet_accounts)
+                }
             })
-            .map_err(|error| {
-                let value: WalletError = error.into();
+            .map_err(connect_error)?
+    }
 
-                WalletError::WalletConnectError(value.to_string())
-            })?
+    /// Check that the `standard:connect` callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.0.is_callable()
     }
 }
@@ -21,15 +21,14 @@ impl Disconnect {
     /// Calling this method disconnects the wallet by internally calling the
     /// callback function
     pub(crate) async fn call_disconnect(&self) -> WalletResult<()> {
-        let outcome = self.0.callback.call0(&JsValue::null())?;
-
-        let outcome = js_sys::Promise::resolve(&outcome);
-
-        if let Some(error) = wasm_bindgen_futures::JsFuture::from(outcome).await.err() {
-            let value: WalletError = error.into();
-            return Err(WalletError::WalletDisconnectError(value.to_string()));
-        }
+        Reflection::call_await(&self.0.callback, &JsValue::null(), &js_sys::Array::new())
+            .await
+            .map(|_| ())
+            .map_err(|error| WalletError::WalletDisconnectError(error.to_string()))
+    }
 
-        Ok(())
+    /// Check that the `standard:disconnect` callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.0.is_callable()
     }
 }
@@ -1,11 +1,13 @@
 use std::{future::Future, pin::Pin};
 
-use async_channel::Receiver;
-use web_sys::wasm_bindgen::{prelude::Closure, JsValue};
+use web_sys::{
+    js_sys::Function,
+    wasm_bindgen::{prelude::Closure, JsValue},
+};
 
 use crate::{
-    ConnectionInfoInner, Reflection, SemverVersion, StandardFunction, WalletAccount, WalletError,
-    WalletEvent, WalletEventSender, WalletResult,
+    ConnectionInfoInner, Reflection, SemverVersion, StandardFunction, WalletAccount, WalletEvent,
+    WalletEventSender, WalletResult,
 };
 
 /// `standard:events` struct containing the `version` and `callback`
@@ -24,38 +26,44 @@ impl StandardEvents {
         }))
     }
 
+    /// Register a listener for the `change` event using the `on` function of `standard:events`.
+    /// The `accounts` of each change payload are translated into a
+    /// [WalletEvent::Connected], [WalletEvent::AccountChanged] or [WalletEvent::Disconnected]
+    /// event which is sent to the `sender`. Payloads without `accounts`, like a change
+    /// of `chains` or `features`, are ignored.
+    /// Returns the unsubscribe function returned by the `on` function
     pub(crate) async fn call_on_event(
         &self,
         connection_info: ConnectionInfoInner,
         wallet_name: String,
         sender: WalletEventSender,
-        stop_signal: Receiver<()>,
-    ) -> WalletResult<()> {
-        let sender2 = sender.clone();
-
+    ) -> WalletResult<Function> {
         let on_account_change = Closure::wrap(Box::new(move |value: JsValue| {
             let wallet_name = wallet_name.clone();
-            web_sys::console::log_3(
-                &"CALLED ON EV for ".into(),
-                &wallet_name.clone().into(),
-                &value,
-            );
-
             let connection_info_inner = connection_info.clone();
-            let sender_inner = sender2.clone();
+            let sender_inner = sender.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                let reflect_accounts =
+                let reflect_properties =
                     send_wallet_event_error()(Reflection::new(value), sender_inner.clone())
                         .await
                         .unwrap(); // Never fails
-                let mut get_accounts = send_wallet_event_error()(
-                    reflect_accounts.reflect_js_array("accounts"),
+
+                let reflect_accounts = send_wallet_event_error()(
+                    reflect_properties.get_optional("accounts"),
                     sender_inner.clone(),
                 )
                 .await
-                .unwrap()
-                .to_vec(); // Never fails
+                .unwrap(); // Never fails
+
+                let mut get_accounts = if let Some(reflect_accounts) = reflect_accounts {
+                    send_wallet_event_error()(reflect_accounts.into_array(), sender_inner.clone())
+                        .await
+                        .unwrap() // Never fails
+                        .to_vec()
+                } else {
+                    return;
+                };
 
                 let processed_wallet_account = if !get_accounts.is_empty() {
                     let first_account = send_wallet_event_error()(
@@ -71,10 +79,6 @@ impl StandardEvents {
                     )
                     .await
                     .unwrap(); //Never fails
-                    web_sys::console::error_2(
-                        &"PRE ACCOUNT PROCESSING".into(),
-                        &format!("{account_processing:?}").into(),
-                    );
 
                     Some(account_processing)
                 } else {
@@ -89,32 +93,18 @@ impl StandardEvents {
             });
         }) as Box<dyn Fn(_)>);
 
-        let on_account_change_fn =
-            Reflection::new(on_account_change.into_js_value())?.into_function()?;
-
-        let on_event_fn = self.0.callback.clone();
-
-        wasm_bindgen_futures::spawn_local(async move {
-            while (stop_signal.recv().await).is_ok() {
-                let invoke_outcome = on_event_fn
-                    .call2(
-                        &JsValue::null(),
-                        &"change".into(),
-                        &on_account_change_fn.clone().into(),
-                    )
-                    .map_err(|error| {
-                        let into_error: WalletError = error.into();
-
-                        into_error
-                    });
+        let unsubscribe = self.0.callback.call2(
+            &JsValue::null(),
+            &"change".into(),
+            &on_account_change.into_js_value(),
+        )?;
 
-                send_wallet_event_error()(invoke_outcome, sender.clone())
-                    .await
-                    .unwrap();
-            }
-        });
+        Reflection::new(unsubscribe)?.into_function()
+    }
 
-        Ok(())
+    /// Check that the `standard:events` callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.0.is_callable()
     }
 }
 
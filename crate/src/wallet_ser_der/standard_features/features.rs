@@ -1,6 +1,6 @@
 use crate::{
     Connect, Disconnect, FeatureSupport, Reflection, SemverVersion, SignIn, SignMessage,
-    SignTransaction, StandardEvents, WalletError, WalletResult,
+    SignTransaction, StandardEvents, Utils, WalletError, WalletResult,
     SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
     SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
     STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
@@ -42,6 +42,9 @@ impl Features {
         features_keys.into_iter().try_for_each(|feature| {
             let inner_object = features_object.reflect_inner(&feature)?;
             let inner_object = Reflection::new(inner_object)?;
+            let feature = Utils::canonical_feature_name(&feature)
+                .map(|canonical| canonical.to_string())
+                .unwrap_or(feature);
 
             if feature.starts_with("standard:") || feature.starts_with("solana:") {
                 let version = SemverVersion::from_jsvalue(&inner_object)?;
@@ -87,4 +90,16 @@ impl Features {
     pub fn extensions(&self) -> &[String] {
         &self.extensions
     }
+
+    /// Check that the callbacks of all the `supported` features are still JS functions.
+    /// This does not call any of the callbacks so it has no side effects on the wallet
+    pub(crate) fn is_callable(&self, supported: &FeatureSupport) -> bool {
+        self.connect.is_callable()
+            && (!supported.disconnect || self.disconnect.is_callable())
+            && (!supported.events || self.events.is_callable())
+            && (!supported.sign_and_send_tx || self.sign_and_send_tx.is_callable())
+            && (!supported.sign_tx || self.sign_tx.is_callable())
+            && (!supported.sign_message || self.sign_message.is_callable())
+            && self.sign_in.as_ref().is_none_or(SignIn::is_callable)
+    }
 }
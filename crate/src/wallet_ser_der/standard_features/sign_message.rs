@@ -23,11 +23,17 @@ impl SignMessage {
         )?))
     }
 
-    /// Internal callback to request a browser wallet to sign a message
+    /// Internal callback to request a browser wallet to sign a message.
+    /// The response is parsed using [Utils::parse_sign_message_response].
+    /// Some wallets let the user switch accounts in the approval popup and return
+    /// the `account` that signed. The signature is verified against that account and
+    /// [WalletError::UnexpectedSigner] is returned if it is not `wallet_account`
+    /// unless `allow_account_switch` is `true`
     pub(crate) async fn call_sign_message<'a>(
         &self,
         wallet_account: &WalletAccount,
         message: &'a [u8],
+        allow_account_switch: bool,
     ) -> WalletResult<SignedMessageOutput<'a>> {
         let message_value: js_sys::Uint8Array = message.into();
 
@@ -53,41 +59,36 @@ impl SignMessage {
             .to_vec();
 
         if let Some(inner) = signed_message_result.first() {
-            let reflect_outcome = Reflection::new(inner.clone())?;
-            let signed_message = reflect_outcome.reflect_inner("signedMessage")?;
-            let signature_value = reflect_outcome.reflect_inner("signature")?;
+            let signed_message =
+                Utils::parse_sign_message_item(inner.clone(), Some(wallet_account.public_key))?;
 
-            let incase_of_error = Err(WalletError::InternalError(format!(
-                "solana:signedMessage -> SignedMessageOutput::signedMessage: Cast `{signed_message:?}` did not yield a JsValue"
-            )));
-
-            let signed_message = Reflection::new(signed_message)?
-                .into_bytes()
-                .or(incase_of_error)?
-                .to_vec();
-
-            if signed_message != message {
+            if signed_message.message() != message {
                 return Err(WalletError::SignedMessageMismatch);
             }
 
-            let signature = Utils::jsvalue_to_signature(
-                signature_value,
-                "solana::signMessage -> SignedMessageOutput::signature",
-            )?;
+            let signer = signed_message.public_key().to_bytes();
 
-            let public_key = Utils::public_key(wallet_account.public_key)?;
-
-            Utils::verify_signature(public_key, message, signature)?;
+            if signer != wallet_account.public_key && !allow_account_switch {
+                return Err(WalletError::UnexpectedSigner {
+                    expected: wallet_account.address.clone(),
+                    signer: signed_message.address(),
+                });
+            }
 
             Ok(SignedMessageOutput {
                 message,
-                public_key: wallet_account.public_key,
-                signature: signature.to_bytes(),
+                public_key: signer,
+                signature: signed_message.signature().to_bytes(),
             })
         } else {
             Err(WalletError::ReceivedAnEmptySignedMessagesArray)
         }
     }
+
+    /// Check that the `solana:signMessage` callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.0.is_callable()
+    }
 }
 
 /// The output of a signed message
@@ -137,3 +138,54 @@ impl Default for SignedMessageOutput<'_> {
         }
     }
 }
+
+/// An owned signed message whose signature has been verified
+/// against the message and the public key of the account that signed it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SignedMessage {
+    message: Vec<u8>,
+    signature: Signature,
+    public_key: VerifyingKey,
+}
+
+impl SignedMessage {
+    pub(crate) fn new(message: Vec<u8>, signature: Signature, public_key: VerifyingKey) -> Self {
+        Self {
+            message,
+            signature,
+            public_key,
+        }
+    }
+
+    /// Get the message that was signed
+    pub fn message(&self) -> &[u8] {
+        self.message.as_slice()
+    }
+
+    /// Get the [Ed25519 Signature](Signature) of the message
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    /// Get the [Ed25519 Public Key](VerifyingKey) that signed the message
+    pub fn public_key(&self) -> VerifyingKey {
+        self.public_key
+    }
+
+    /// Get the Base58 address of the [Ed25519 Public Key](VerifyingKey) that signed the message
+    pub fn address(&self) -> String {
+        Utils::address(self.public_key)
+    }
+}
+
+impl TryFrom<SignedMessageOutput<'_>> for SignedMessage {
+    type Error = WalletError;
+
+    fn try_from(value: SignedMessageOutput<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            message: value.message.to_vec(),
+            signature: value.signature(),
+            public_key: value.public_key()?,
+        })
+    }
+}
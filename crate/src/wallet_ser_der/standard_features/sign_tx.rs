@@ -1,7 +1,7 @@
 use ed25519_dalek::Signature;
 use web_sys::{
     js_sys::{self, Function},
-    wasm_bindgen::JsValue,
+    wasm_bindgen::{JsCast, JsValue},
 };
 
 use core::hash::Hash;
@@ -11,7 +11,7 @@ use crate::{
 };
 
 /// Used in `solana:SignTransaction` and `solana:SignAndSendTransaction`.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SignTransaction {
     /// The [semver version](SemverVersion) of the
     /// callback function supported by the wallet
@@ -63,6 +63,12 @@ impl SignTransaction {
         Self::new(reflection, version, "signAndSendTransaction")
     }
 
+    /// Check that the `solana:signTransaction` or `solana:signAndSendTransaction`
+    /// callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.callback.is_function()
+    }
+
     fn get_tx_version_support(inner_value: &Reflection) -> WalletResult<(bool, bool)> {
         let tx_version_support_jsvalue = inner_value
             .reflect_inner("supportedTransactionVersions")
@@ -93,12 +99,12 @@ impl SignTransaction {
         Ok((legacy, version_zero))
     }
 
-    pub(crate) async fn call_sign_tx(
-        &self,
+    /// Construct the `solana:signTransaction` input object for one transaction
+    fn sign_tx_input(
         wallet_account: &WalletAccount,
         transaction_bytes: &[u8],
         cluster: Option<Cluster>,
-    ) -> WalletResult<Vec<Vec<u8>>> {
+    ) -> WalletResult<JsValue> {
         let tx_bytes_value: js_sys::Uint8Array = transaction_bytes.into();
 
         let mut tx_object = Reflection::new_object();
@@ -108,7 +114,18 @@ impl SignTransaction {
             tx_object.set_object(&"chain".into(), &cluster.chain().into())?;
         }
 
-        let outcome = self.callback.call1(&JsValue::null(), &tx_object.take())?;
+        Ok(tx_object.take())
+    }
+
+    pub(crate) async fn call_sign_tx(
+        &self,
+        wallet_account: &WalletAccount,
+        transaction_bytes: &[u8],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        let tx_object = Self::sign_tx_input(wallet_account, transaction_bytes, cluster)?;
+
+        let outcome = self.callback.call1(&JsValue::null(), &tx_object)?;
 
         let outcome = js_sys::Promise::resolve(&outcome);
 
@@ -116,6 +133,39 @@ impl SignTransaction {
         Reflection::new(success)?.get_bytes_from_vec("signedTransaction")
     }
 
+    /// Sign multiple transactions in one request. The `solana:signTransaction`
+    /// callback accepts one input per transaction and returns the outputs in the same order
+    pub(crate) async fn call_sign_all_tx(
+        &self,
+        wallet_account: &WalletAccount,
+        transactions: &[Vec<u8>],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        let inputs = transactions
+            .iter()
+            .map(|transaction_bytes| {
+                Self::sign_tx_input(wallet_account, transaction_bytes, cluster)
+            })
+            .collect::<WalletResult<js_sys::Array>>()?;
+
+        let outcome = self.callback.apply(&JsValue::null(), &inputs)?;
+
+        let outcome = js_sys::Promise::resolve(&outcome);
+
+        let success = wasm_bindgen_futures::JsFuture::from(outcome).await?;
+        let signed_transactions =
+            Reflection::new(success)?.get_bytes_from_vec("signedTransaction")?;
+
+        if signed_transactions.len() != transactions.len() {
+            return Err(WalletError::SignedTransactionsCountMismatch {
+                expected: transactions.len(),
+                received: signed_transactions.len(),
+            });
+        }
+
+        Ok(signed_transactions)
+    }
+
     pub(crate) async fn call_sign_and_send_transaction(
         &self,
         wallet_account: &WalletAccount,
@@ -148,9 +198,20 @@ impl SignTransaction {
     }
 }
 
+impl Default for SignTransaction {
+    fn default() -> Self {
+        Self {
+            version: SemverVersion::default(),
+            legacy: bool::default(),
+            version_zero: bool::default(),
+            callback: JsValue::undefined().unchecked_into(),
+        }
+    }
+}
+
 impl PartialOrd for SignTransaction {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.version.cmp(&other.version))
+        Some(self.cmp(other))
     }
 }
 
@@ -171,23 +232,164 @@ impl Hash for SignTransaction {
 /// - [preflight_commitment](Commitment)
 /// - [skip_preflight](bool)
 /// - [max_retries](u8)
+/// - [min_context_slot](u64)
+///
+/// Only the options that are set are sent to the browser wallet
+/// so that the wallet applies its own defaults for the rest.
+/// #### Example
+/// ```rust
+/// use wallet_adapter::{Commitment, SendOptions};
+///
+/// let mut options = SendOptions::default();
+/// options
+///     .set_preflight_commitment(Commitment::Confirmed)
+///     .set_max_retries(3);
+///
+/// assert_eq!(options.preflight_commitment(), Some(Commitment::Confirmed));
+/// assert_eq!(options.skip_preflight(), None);
+/// ```
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct SendOptions {
-    preflight_commitment: Commitment,
-    skip_preflight: bool,
-    max_retries: u8,
+    preflight_commitment: Option<Commitment>,
+    skip_preflight: Option<bool>,
+    max_retries: Option<u8>,
+    min_context_slot: Option<u64>,
 }
 
 impl SendOptions {
+    /// The commitment level to use for the preflight simulation
+    pub fn set_preflight_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.preflight_commitment.replace(commitment);
+
+        self
+    }
+
+    /// Whether to skip the preflight simulation
+    pub fn set_skip_preflight(&mut self, skip_preflight: bool) -> &mut Self {
+        self.skip_preflight.replace(skip_preflight);
+
+        self
+    }
+
+    /// The maximum number of times the RPC node retries sending the transaction
+    pub fn set_max_retries(&mut self, max_retries: u8) -> &mut Self {
+        self.max_retries.replace(max_retries);
+
+        self
+    }
+
+    /// The minimum slot at which the preflight simulation is performed
+    pub fn set_min_context_slot(&mut self, min_context_slot: u64) -> &mut Self {
+        self.min_context_slot.replace(min_context_slot);
+
+        self
+    }
+
+    /// Get the `preflight_commitment` option
+    pub fn preflight_commitment(&self) -> Option<Commitment> {
+        self.preflight_commitment
+    }
+
+    /// Get the `skip_preflight` option
+    pub fn skip_preflight(&self) -> Option<bool> {
+        self.skip_preflight
+    }
+
+    /// Get the `max_retries` option
+    pub fn max_retries(&self) -> Option<u8> {
+        self.max_retries
+    }
+
+    /// Get the `min_context_slot` option
+    pub fn min_context_slot(&self) -> Option<u64> {
+        self.min_context_slot
+    }
+
     /// Converts [SendOptions] to a [JsValue] which can be passed
     /// to the browser wallet when making requests.
-    /// Internally, it is a [js_sys::Object]
+    /// Internally, it is a [js_sys::Object] containing only the options that are set,
+    /// the default [SendOptions] is an empty object
     pub fn to_object(&self) -> WalletResult<JsValue> {
         let mut reflection = Reflection::new_object();
-        reflection.set_object_str("preflightCommitment", self.preflight_commitment.as_str())?;
-        reflection.set_object(&"skipPreflight".into(), &JsValue::from(self.skip_preflight))?;
-        reflection.set_object(&"maxRetries".into(), &JsValue::from(self.max_retries))?;
+
+        if let Some(preflight_commitment) = self.preflight_commitment {
+            reflection.set_object_str("preflightCommitment", preflight_commitment.as_str())?;
+        }
+        if let Some(skip_preflight) = self.skip_preflight {
+            reflection.set_object(&"skipPreflight".into(), &JsValue::from(skip_preflight))?;
+        }
+        if let Some(max_retries) = self.max_retries {
+            reflection.set_object(&"maxRetries".into(), &JsValue::from(max_retries))?;
+        }
+        if let Some(min_context_slot) = self.min_context_slot {
+            // JS numbers are used for slots since a `u64` converts to a `BigInt`
+            reflection.set_object(
+                &"minContextSlot".into(),
+                &JsValue::from_f64(min_context_slot as f64),
+            )?;
+        }
 
         Ok(reflection.take())
     }
 }
+
+/// A transaction that was signed and sent by the browser wallet
+/// as part of a batch using [crate::WalletAdapter::sign_and_send_all]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SentTransaction {
+    index: usize,
+    signature: Signature,
+}
+
+impl SentTransaction {
+    /// Instantiate a new [SentTransaction]
+    pub fn new(index: usize, signature: Signature) -> Self {
+        Self { index, signature }
+    }
+
+    /// The position of the transaction in the batch
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The Ed25519 signature of the sent transaction
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod send_options_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn to_object() {
+        let options = Reflection::new(SendOptions::default().to_object().unwrap()).unwrap();
+        assert!(options.keys().unwrap().is_empty());
+
+        let mut send_options = SendOptions::default();
+        send_options
+            .set_preflight_commitment(Commitment::Confirmed)
+            .set_skip_preflight(true)
+            .set_max_retries(3)
+            .set_min_context_slot(42);
+        let options = Reflection::new(send_options.to_object().unwrap()).unwrap();
+
+        assert_eq!(
+            options.keys().unwrap(),
+            vec![
+                "preflightCommitment".to_string(),
+                "skipPreflight".to_string(),
+                "maxRetries".to_string(),
+                "minContextSlot".to_string()
+            ]
+        );
+        assert_eq!(options.string("preflightCommitment").unwrap(), "confirmed");
+        assert_eq!(
+            options.reflect_inner("minContextSlot").unwrap().as_f64(),
+            Some(42f64)
+        );
+    }
+}
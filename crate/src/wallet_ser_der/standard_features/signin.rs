@@ -18,10 +18,12 @@ impl SignIn {
         )?))
     }
 
+    /// Send the sign in request. The signature is verified against `public_key`
+    /// or against the public key of the account returned by the wallet if `public_key` is `None`
     pub(crate) async fn call_signin(
         &self,
         signin_input: &SigninInput,
-        public_key: [u8; 32],
+        public_key: Option<[u8; 32]>,
     ) -> WalletResult<SignInOutput> {
         let outcome = self
             .0
@@ -36,6 +38,7 @@ impl SignIn {
         let first_index = Reflection::new(output_array.get(0))?;
         let account = first_index.reflect_inner("account")?;
         let account = WalletAccount::parse(Reflection::new(account)?)?;
+        let public_key = public_key.unwrap_or(account.public_key);
 
         let message_value = first_index.reflect_inner("signedMessage")?;
         let message_bytes = Reflection::new(message_value)?.into_bytes()?;
@@ -63,4 +66,9 @@ impl SignIn {
             public_key,
         })
     }
+
+    /// Check that the `solana:signIn` callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.0.is_callable()
+    }
 }
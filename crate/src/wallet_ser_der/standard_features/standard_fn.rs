@@ -1,13 +1,16 @@
 use core::hash::Hash;
 
-use web_sys::js_sys::Function;
+use web_sys::{
+    js_sys::Function,
+    wasm_bindgen::{JsCast, JsValue},
+};
 
 use crate::{Reflection, SemverVersion, WalletError, WalletResult};
 
 /// A struct containing the [semver version](SemverVersion)
 /// and [callback function](Function) within the `standard:` namespace as
 /// defined by the wallet standard
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StandardFunction {
     pub(crate) version: SemverVersion,
     pub(crate) callback: Function,
@@ -39,11 +42,25 @@ impl StandardFunction {
             callback: get_fn,
         })
     }
+
+    /// Check that the callback is still a JS function
+    pub(crate) fn is_callable(&self) -> bool {
+        self.callback.is_function()
+    }
+}
+
+impl Default for StandardFunction {
+    fn default() -> Self {
+        Self {
+            version: SemverVersion::default(),
+            callback: JsValue::undefined().unchecked_into(),
+        }
+    }
 }
 
 impl PartialOrd for StandardFunction {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.version.cmp(&other.version))
+        Some(self.cmp(other))
     }
 }
 
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 
-use crate::{Reflection, WalletError, WalletResult};
+#[cfg(feature = "browser")]
+use crate::Reflection;
+use crate::{WalletError, WalletResult};
 
 /// Semver Versioning struct
 #[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -26,7 +28,8 @@ impl SemverVersion {
         self.patch
     }
 
-    /// Parse the version from a [JsValue]
+    /// Parse the version from a [JsValue](web_sys::wasm_bindgen::JsValue)
+    #[cfg(feature = "browser")]
     pub(crate) fn from_jsvalue(reflection: &Reflection) -> WalletResult<Self> {
         let version = reflection
             .reflect_inner("version")
@@ -63,8 +66,21 @@ impl SemverVersion {
         })
     }
 
+    /// Check that [Self] is compatible with the `required` semver version string.
+    /// The versions are compatible if their major versions match.
+    /// Before `1.0.0` the minor versions also have to match since a minor version
+    /// may contain breaking changes.
+    /// Returns `false` if `required` is not a valid semver version
+    pub fn is_compatible_with(&self, required: &str) -> bool {
+        match Self::parse(required) {
+            Ok(required) if required.major == 0 => self.major == 0 && self.minor == required.minor,
+            Ok(required) => self.major == required.major,
+            Err(_) => false,
+        }
+    }
+
     /// Get the string version of [Self] in the format `major.minor.patch`
-    pub fn stringify_version(&self) -> Cow<str> {
+    pub fn stringify_version(&self) -> Cow<'_, str> {
         Cow::Borrowed("")
             + Cow::Owned(self.major.to_string())
             + "."
@@ -89,3 +105,22 @@ impl core::fmt::Display for SemverVersion {
         write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
     }
 }
+
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+
+    #[test]
+    fn is_compatible_with() {
+        let version = SemverVersion::parse("1.2.3").unwrap();
+        assert!(version.is_compatible_with(crate::WALLET_STANDARD_VERSION));
+        assert!(version.is_compatible_with("1.9.0"));
+        assert!(!version.is_compatible_with("2.0.0"));
+        assert!(!version.is_compatible_with("1.0"));
+
+        let version = SemverVersion::parse("0.2.3").unwrap();
+        assert!(version.is_compatible_with("0.2.0"));
+        assert!(!version.is_compatible_with("0.3.0"));
+        assert!(!version.is_compatible_with("1.0.0"));
+    }
+}
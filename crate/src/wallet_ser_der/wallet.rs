@@ -1,18 +1,23 @@
-use async_channel::Receiver;
 use ed25519_dalek::Signature;
-use web_sys::wasm_bindgen::JsValue;
+use web_sys::{js_sys::Function, wasm_bindgen::JsValue};
 
 use crate::{
-    Cluster, ConnectionInfoInner, Features, Reflection, SemverVersion, WalletAccount, WalletError,
-    WalletEventSender, WalletIcon, WalletResult,
+    Cluster, ConnectionInfoInner, Features, Reflection, SemverVersion, Utils, WalletAccount,
+    WalletError, WalletEventSender, WalletIcon, WalletResult,
+    SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
+    SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+    STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
 };
 
 use super::{
     ChainSupport, FeatureSupport, SendOptions, SignInOutput, SignedMessageOutput, SigninInput,
 };
 
-/// A wallet implementing wallet standard
-#[derive(Clone, Default, PartialEq, Eq)]
+/// A wallet implementing wallet standard.
+/// Wallets are compared and hashed by their identity, see [Wallet::id],
+/// so two wallets are equal if and only if the [blake3] hashes of their lowercase names are equal.
+/// This matches the default deduplication of wallets in the [crate::WalletStorage]
+#[derive(Clone, Default)]
 pub struct Wallet {
     name: String,
     version: SemverVersion,
@@ -24,27 +29,82 @@ pub struct Wallet {
     supported_features: FeatureSupport,
     // Convenience field, instead of iteration through the `chains` field
     supported_chains: ChainSupport,
+    // The JavaScript value the wallet registered itself with
+    js_value: JsValue,
 }
 
 impl Wallet {
     /// Send a request to connect to a browser wallet
+    /// and return the first account, see [Self::connect_all]
     pub async fn connect(&self) -> WalletResult<WalletAccount> {
+        let mut accounts = self.connect_all().await?;
+
+        Ok(accounts.remove(0)) // The accounts returned by a connect request are never empty
+    }
+
+    /// Send a request to connect to a browser wallet and return all the accounts
+    /// returned by the wallet. Some wallets return more than one account,
+    /// the first account is the primary account
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
+    pub async fn connect_all(&self) -> WalletResult<Vec<WalletAccount>> {
         self.features.connect.call_connect().await
     }
 
+    /// Send a request to silently connect to a browser wallet.
+    /// The user is not prompted and only the accounts the app is already
+    /// authorized to use are returned.
+    /// Returns [WalletError::SilentConnectUnsupported] if the wallet does not support
+    /// the `silent` input
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
+    pub async fn connect_silent(&self) -> WalletResult<Vec<WalletAccount>> {
+        self.features.connect.call_connect_silent().await
+    }
+
     /// Send a request to the browser wallet to disconnect
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
     pub async fn disconnect(&self) -> WalletResult<()> {
         self.features.disconnect.call_disconnect().await
     }
 
     /// Send a signin request to the browser wallet
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
     pub async fn sign_in(
         &self,
         signin_input: &SigninInput,
         public_key: [u8; 32],
     ) -> WalletResult<SignInOutput> {
         if let Some(fn_exists) = self.features.sign_in.as_ref() {
-            fn_exists.call_signin(signin_input, public_key).await
+            fn_exists.call_signin(signin_input, Some(public_key)).await
+        } else {
+            Err(WalletError::MissingSignInFunction)
+        }
+    }
+
+    /// Send a sign in request to the browser wallet without a connected account.
+    /// The wallet selects the account and the signature is verified against
+    /// the public key of the [account](WalletAccount) it returns
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
+    pub async fn sign_in_with_account(
+        &self,
+        signin_input: &SigninInput,
+    ) -> WalletResult<SignInOutput> {
+        if let Some(fn_exists) = self.features.sign_in.as_ref() {
+            fn_exists.call_signin(signin_input, None).await
         } else {
             Err(WalletError::MissingSignInFunction)
         }
@@ -52,6 +112,10 @@ impl Wallet {
 
     /// Send a sign message request to the browser wallet.
     /// Message must be UTF-8 encoded
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
     pub async fn sign_message<'a>(
         &self,
         message: &'a [u8],
@@ -59,12 +123,16 @@ impl Wallet {
     ) -> WalletResult<SignedMessageOutput<'a>> {
         self.features
             .sign_message
-            .call_sign_message(account, message)
+            .call_sign_message(account, message, false)
             .await
     }
 
     /// Send a sign transaction request to the browser wallet.
     /// The transaction bytes expected are encoded using serde in byte form.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
     pub async fn sign_transaction(
         &self,
         transaction_bytes: &[u8],
@@ -77,7 +145,29 @@ impl Wallet {
             .await
     }
 
+    /// Send a request to the browser wallet to sign multiple transactions at once.
+    /// The signed transactions are returned in the same order as the `transactions`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
+    pub async fn sign_all_transactions(
+        &self,
+        transactions: &[Vec<u8>],
+        cluster: Option<Cluster>,
+        account: &WalletAccount,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        self.features
+            .sign_tx
+            .call_sign_all_tx(account, transactions, cluster)
+            .await
+    }
+
     /// Send a sign and send transaction request to the browser wallet.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(wallet = %self.name), err)
+    )]
     pub async fn sign_and_send_transaction(
         &self,
         transaction_bytes: &[u8],
@@ -91,24 +181,38 @@ impl Wallet {
             .await
     }
 
-    /// Get the standard events [Function](web_sys::js_sys::Function) `[standard:events].on`
+    /// Register a `change` listener using the standard events [Function](web_sys::js_sys::Function)
+    /// `[standard:events].on` and return the unsubscribe [Function](web_sys::js_sys::Function)
     pub async fn call_on_event(
         &self,
         connection_info: ConnectionInfoInner,
         wallet_name: String,
         sender: WalletEventSender,
-        signal_receiver: Receiver<()>,
-    ) -> WalletResult<()> {
+    ) -> WalletResult<Function> {
         self.features
             .events
-            .call_on_event(connection_info, wallet_name, sender, signal_receiver)
+            .call_on_event(connection_info, wallet_name, sender)
             .await
     }
 
-    /// Parse the Wallet details from a [JsValue]
+    /// Parse the Wallet details from a [JsValue].
+    /// A missing `name` or `version`, an empty `chains` array or an `accounts` entry
+    /// that cannot be parsed return a [WalletError::MalformedWallet] describing the field
     pub fn from_jsvalue(value: JsValue) -> WalletResult<Self> {
         let reflection = Reflection::new(value)?;
 
+        let name = reflection
+            .string("name")
+            .or(Err(WalletError::MalformedWallet(
+                "missing `name`".to_string(),
+            )))?;
+        let version = reflection
+            .string("version")
+            .or(Err(WalletError::MalformedWallet(format!(
+                "`{name}` is missing `version`"
+            ))))?;
+        let version = SemverVersion::parse(&version)?;
+
         let mut supported_chains = ChainSupport::default();
 
         let chains_raw = reflection.vec_string_and_filter("chains", "solana:")?;
@@ -132,10 +236,20 @@ impl Wallet {
             })
             .collect::<WalletResult<Vec<Cluster>>>()?;
 
-        let name = reflection.string("name")?;
-        let version = SemverVersion::parse(&reflection.string("version")?)?;
+        if chains.is_empty() {
+            return Err(WalletError::MalformedWallet(format!(
+                "`{name}` has an empty `chains` array"
+            )));
+        }
+
         let icon = WalletIcon::from_jsvalue(&reflection)?;
-        let accounts = Self::get_accounts(&reflection, "accounts")?;
+        let accounts =
+            Self::get_accounts(&reflection, "accounts").map_err(|error| match error {
+                WalletError::MalformedWallet(reason) => {
+                    WalletError::MalformedWallet(format!("`{name}` {reason}"))
+                }
+                error => error,
+            })?;
         let (features, supported_features) = Features::parse(&reflection)?;
 
         Ok(Wallet {
@@ -147,6 +261,7 @@ impl Wallet {
             features,
             supported_features,
             supported_chains,
+            js_value: reflection.take(),
         })
     }
 
@@ -157,7 +272,16 @@ impl Wallet {
 
         accounts_array
             .iter()
-            .map(|account| WalletAccount::parse(Reflection::new(account)?))
+            .enumerate()
+            .map(|(index, account)| {
+                Reflection::new(account)
+                    .and_then(WalletAccount::parse)
+                    .map_err(|error| {
+                        WalletError::MalformedWallet(format!(
+                            "has an `accounts` entry at index {index} that cannot be parsed: {error}"
+                        ))
+                    })
+            })
             .collect::<WalletResult<Vec<WalletAccount>>>()
     }
 
@@ -166,11 +290,35 @@ impl Wallet {
         &self.features
     }
 
-    /// Get the accounts provided by the wallet
+    /// Get the object of the feature `key` of the wallet, for example a wallet specific
+    /// extension like `vendor:stake`, to reflect and call its methods directly.
+    /// This is an escape hatch for features not modelled by the typed accessors.
+    /// Returns [WalletError::ExpectedValueNotFound] if the wallet does not have the feature
+    pub fn feature(&self, key: &str) -> WalletResult<Reflection> {
+        Reflection::new_from_path(&self.js_value, &["features", key])
+    }
+
+    /// Get the `version` of the feature `key` of the wallet, for example `1.0.0`
+    /// for `solana:signMessage`, since each feature of the wallet standard is versioned separately.
+    /// Returns [WalletError::ExpectedValueNotFound] if the wallet does not have the feature
+    pub fn feature_version(&self, key: &str) -> WalletResult<String> {
+        self.feature(key)?.string("version")
+    }
+
+    /// Get the accounts provided by the wallet when it was registered
     pub fn accounts(&self) -> &[WalletAccount] {
         &self.accounts
     }
 
+    /// Parse the `accounts` the wallet currently exposes, which are the accounts the app
+    /// is already authorized to use. These can change after the wallet was registered,
+    /// for example after a connect request in a previous session, so they are read from the
+    /// wallet each time. An app can use them to show a "continue as" option without
+    /// prompting the user through a connect request
+    pub fn authorized_accounts(&self) -> WalletResult<Vec<WalletAccount>> {
+        Self::get_accounts(&Reflection::new(self.js_value.clone())?, "accounts")
+    }
+
     /// Get the chains supported by the wallet
     pub fn chains(&self) -> &[Cluster] {
         &self.chains
@@ -231,6 +379,67 @@ impl Wallet {
         self.supported_features.sign_tx
     }
 
+    /// Check whether the wallet advertises the `feature`, for example `solana:signAndSendTransaction`
+    /// or a non-standard extension. Standard feature names are matched after
+    /// normalization using [Utils::canonical_feature_name]
+    pub fn supports_feature(&self, feature: &str) -> bool {
+        match Utils::canonical_feature_name(feature) {
+            Some(STANDARD_CONNECT_IDENTIFIER) => self.standard_connect(),
+            Some(STANDARD_DISCONNECT_IDENTIFIER) => self.standard_disconnect(),
+            Some(STANDARD_EVENTS_IDENTIFIER) => self.standard_events(),
+            Some(SOLANA_SIGN_IN_IDENTIFIER) => self.solana_signin(),
+            Some(SOLANA_SIGN_MESSAGE_IDENTIFIER) => self.solana_sign_message(),
+            Some(SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER) => {
+                self.solana_sign_and_send_transaction()
+            }
+            Some(SOLANA_SIGN_TRANSACTION_IDENTIFIER) => self.solana_sign_transaction(),
+            _ => self
+                .features
+                .extensions()
+                .iter()
+                .any(|extension| extension == feature),
+        }
+    }
+
+    /// Probe the wallet without sending a request to the browser wallet by checking
+    /// that the callbacks of the supported features are still callable.
+    /// Returns [WalletError::WalletUnresponsive] if any of them is no longer a JS function
+    pub(crate) fn health_check(&self) -> WalletResult<()> {
+        if self.features.is_callable(&self.supported_features) {
+            Ok(())
+        } else {
+            Err(WalletError::WalletUnresponsive(self.name.clone()))
+        }
+    }
+
+    /// The number of `standard:` and `solana:` features supported by the wallet
+    /// together with the non-standard extensions
+    pub(crate) fn features_count(&self) -> usize {
+        let FeatureSupport {
+            connect,
+            disconnect,
+            events,
+            sign_in,
+            sign_message,
+            sign_and_send_tx,
+            sign_tx,
+        } = self.supported_features;
+
+        [
+            connect,
+            disconnect,
+            events,
+            sign_in,
+            sign_message,
+            sign_and_send_tx,
+            sign_tx,
+        ]
+        .into_iter()
+        .filter(|supported| *supported)
+        .count()
+            + self.features.extensions().len()
+    }
+
     /// Get the optional [wallet icon](WalletIcon)
     pub fn icon(&self) -> Option<&WalletIcon> {
         self.icon.as_ref()
@@ -241,10 +450,31 @@ impl Wallet {
         &self.name
     }
 
+    /// The identity of the wallet which is the [blake3] hash of the lowercase name of the wallet,
+    /// the same key the [crate::WalletStorage] stores the wallet under by default
+    pub fn id(&self) -> blake3::Hash {
+        blake3::hash(self.name.to_lowercase().as_bytes())
+    }
+
     /// Get the version of the wallet standard that the wallet supports
     pub fn version(&self) -> &SemverVersion {
         &self.version
     }
+
+    /// Check that the version of the wallet standard implemented by the wallet
+    /// is compatible with the `required` version, usually [crate::WALLET_STANDARD_VERSION].
+    /// See [SemverVersion::is_compatible_with]
+    pub fn is_compatible_with(&self, required: &str) -> bool {
+        self.version.is_compatible_with(required)
+    }
+
+    #[cfg(all(test, target_arch = "wasm32"))]
+    pub(crate) fn with_name(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
 }
 
 impl core::fmt::Debug for Wallet {
@@ -266,6 +496,14 @@ impl core::fmt::Debug for Wallet {
     }
 }
 
+impl PartialEq for Wallet {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for Wallet {}
+
 impl PartialOrd for Wallet {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -274,16 +512,65 @@ impl PartialOrd for Wallet {
 
 impl Ord for Wallet {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.name
-            .as_bytes()
-            .cmp(other.name.as_bytes())
-            .then(self.version.cmp(&other.version))
+        self.name.to_lowercase().cmp(&other.name.to_lowercase())
     }
 }
 
 impl core::hash::Hash for Wallet {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.name.as_bytes().hash(state);
-        self.version.hash(state);
+        self.id().hash(state);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_tests {
+    use web_sys::js_sys::Array;
+
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn identity() {
+        use std::collections::HashSet;
+
+        let wallets = [
+            Wallet::with_name("Solflare"),
+            Wallet::with_name("solflare"),
+            Wallet::with_name("Phantom"),
+        ];
+
+        assert_eq!(wallets[0], wallets[1]);
+        assert_ne!(wallets[0], wallets[2]);
+        assert_eq!(wallets[0].cmp(&wallets[1]), std::cmp::Ordering::Equal);
+        assert_eq!(wallets.into_iter().collect::<HashSet<Wallet>>().len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn malformed_wallet() {
+        let mut wallet = Reflection::new_object();
+        assert_eq!(
+            Wallet::from_jsvalue(wallet.get_inner().clone()),
+            Err(WalletError::MalformedWallet("missing `name`".to_string()))
+        );
+
+        wallet.set_object_str("name", "Foo").unwrap();
+        assert_eq!(
+            Wallet::from_jsvalue(wallet.get_inner().clone()),
+            Err(WalletError::MalformedWallet(
+                "`Foo` is missing `version`".to_string()
+            ))
+        );
+
+        wallet.set_object_str("version", "1.0.0").unwrap();
+        wallet
+            .set_object(&"chains".into(), &Array::new().into())
+            .unwrap();
+        assert_eq!(
+            Wallet::from_jsvalue(wallet.take()),
+            Err(WalletError::MalformedWallet(
+                "`Foo` has an empty `chains` array".to_string()
+            ))
+        );
     }
 }
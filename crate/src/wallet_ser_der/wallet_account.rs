@@ -1,16 +1,21 @@
 use std::borrow::Cow;
 
+use ed25519_dalek::Signature;
 use web_sys::wasm_bindgen::JsValue;
 
 use crate::{
-    Reflection, Utils, WalletError, WalletIcon, WalletResult,
-    SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
+    PublicKeyBytes, Reflection, SendOptions, SignedMessage, Utils, Wallet, WalletError, WalletIcon,
+    WalletResult, SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
     SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
     STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
 };
 
 use super::{ChainSupport, Cluster, FeatureSupport};
 
+/// The delay in milliseconds before the first retry of
+/// [WalletAccount::sign_and_send_with_retry], doubled after every retry
+pub const RETRY_BASE_DELAY_MS: u32 = 500;
+
 /// Interface of a **WalletAccount**, also referred to as an **Account**.
 /// An account is a _read-only data object_ that is provided from the Wallet to the app,
 /// authorizing the app to use it.
@@ -35,6 +40,9 @@ pub struct WalletAccount {
     /// The Javascript Value Representation of a wallet,
     /// this mostly used internally in the wallet adapter
     pub(crate) js_value: JsValue,
+    /// The time in milliseconds since the UNIX epoch, as reported by [Utils::now_millis],
+    /// when the account was set as the connected account of the [crate::WalletAdapter]
+    pub(crate) connected_at: Option<f64>,
     // Convenience field, instead of going through the `features` field
     supported_features: FeatureSupport,
     // Convenience field, instead of iteration through the `chains` field
@@ -52,6 +60,12 @@ impl WalletAccount {
         self.public_key
     }
 
+    /// The raw 32 bytes of the public key of the account,
+    /// for example to derive a program derived address
+    pub fn public_key_bytes(&self) -> PublicKeyBytes {
+        self.public_key
+    }
+
     /// Chains supported by the account.
     /// This must be a subset of the {@link Wallet.chains | chains} of the Wallet.
     pub fn chains(&self) -> &[String] {
@@ -65,8 +79,8 @@ impl WalletAccount {
     }
 
     /// Optional user-friendly descriptive label or name for the account. This may be displayed by the app.
-    pub fn label(&self) -> Option<&String> {
-        self.label.as_ref()
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
     }
 
     /// An optional [WalletIcon]
@@ -74,25 +88,50 @@ impl WalletAccount {
         self.icon.as_ref()
     }
 
+    /// The time in milliseconds since the UNIX epoch when the account was connected.
+    /// Returns `None` if the account has never been connected.
+    pub fn connected_at(&self) -> Option<f64> {
+        self.connected_at
+    }
+
+    /// How long in milliseconds the account has been connected,
+    /// computed as `Date.now() - connected_at`.
+    /// Returns `None` if the account has never been connected.
+    pub fn connected_duration_ms(&self) -> Option<f64> {
+        self.connected_at
+            .map(|connected_at| Utils::now_millis() - connected_at)
+    }
+
+    /// Record the current time as the time the account was connected
+    pub(crate) fn set_connected_now(&mut self) -> &mut Self {
+        self.connected_at.replace(Utils::now_millis());
+
+        self
+    }
+
     /// Get the shortened address of the `Base58 address` .
     /// It displays the first 4 characters and the last for characters
     /// separated by ellipsis eg `FXdl...RGd4` .
     /// If the address is less than 8 characters, an error is thrown
-    pub fn shorten_address(&self) -> WalletResult<Cow<str>> {
+    pub fn shorten_address(&self) -> WalletResult<Cow<'_, str>> {
         Utils::shorten_base58(&self.address)
     }
 
     /// Same as [Self::shorten_address] but with a custom range
     /// instead of taking the first 4 character and the last 4 characters
     /// it uses a custom range.
-    pub fn custom_shorten_address(&self, take: usize) -> WalletResult<Cow<str>> {
+    pub fn custom_shorten_address(&self, take: usize) -> WalletResult<Cow<'_, str>> {
         Utils::custom_shorten_base58(&self.address, take)
     }
 
     /// Same as [Self::shorten_address] but with a custom range
     /// instead of taking the first 4 character and the last 4 characters
     /// it uses a custom range for first characters before ellipsis and last characters after ellipsis.
-    pub fn custom_shorten_address_rl(&self, left: usize, right: usize) -> WalletResult<Cow<str>> {
+    pub fn custom_shorten_address_rl(
+        &self,
+        left: usize,
+        right: usize,
+    ) -> WalletResult<Cow<'_, str>> {
         if self.address.len() < left + right {
             return Err(WalletError::InvalidBase58Address);
         }
@@ -103,6 +142,155 @@ impl WalletAccount {
         Ok(Cow::Borrowed(first_part) + "..." + last_part)
     }
 
+    /// Request the browser `wallet` to sign a `message` using this account.
+    /// The account does not hold the callbacks of the wallet so the `wallet`
+    /// this account belongs to is required.
+    /// The signature returned by the wallet is verified against the message using
+    /// [Utils::verify_signature] before returning so misbehaving wallets are caught early.
+    /// Returns [WalletError::UnexpectedSigner] if the user switched to another account
+    /// in the wallet popup, see [Self::sign_message_with_account_switch]
+    pub async fn sign_message(
+        &self,
+        wallet: &Wallet,
+        message: &[u8],
+    ) -> WalletResult<SignedMessage> {
+        self.sign_message_with_account_switch(wallet, message, false)
+            .await
+    }
+
+    /// Same as [Self::sign_message] but if `allow_account_switch` is `true` a signature
+    /// from another account the user switched to in the wallet popup is accepted.
+    /// The signature is always verified against the account that signed, which is
+    /// returned by [SignedMessage::public_key]
+    pub async fn sign_message_with_account_switch(
+        &self,
+        wallet: &Wallet,
+        message: &[u8],
+        allow_account_switch: bool,
+    ) -> WalletResult<SignedMessage> {
+        if !self.solana_sign_message() || !wallet.solana_sign_message() {
+            return Err(WalletError::MissingSignMessageFunction);
+        }
+
+        wallet
+            .features
+            .sign_message
+            .call_sign_message(self, message, allow_account_switch)
+            .await?
+            .try_into()
+    }
+
+    /// Request the browser `wallet` to sign a serialized transaction using this account
+    /// on the `chain`, for example `solana:devnet`, and return the signed transaction bytes.
+    /// Returns [WalletError::UnsupportedChain] if the account does not support the `chain`
+    pub async fn sign_transaction(
+        &self,
+        wallet: &Wallet,
+        transaction_bytes: &[u8],
+        chain: &str,
+    ) -> WalletResult<Vec<u8>> {
+        if !self.supports_chain(chain) {
+            return Err(WalletError::UnsupportedChain(chain.to_string()));
+        }
+
+        if !wallet.solana_sign_transaction() {
+            return Err(WalletError::MissingSignTransactionFunction);
+        }
+
+        wallet
+            .sign_transaction(transaction_bytes, Some(chain.try_into()?), self)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(WalletError::SignedTransactionEmpty)
+    }
+
+    /// Request the browser `wallet` to sign all the serialized `transactions` in one request
+    /// using this account on the `chain`. Wallets implement this through the
+    /// `solana:signTransaction` feature which accepts multiple transactions.
+    /// The signed transactions are returned in the same order as the `transactions`
+    /// and an error is returned if the wallet returns a different number of transactions
+    pub async fn sign_all_transactions(
+        &self,
+        wallet: &Wallet,
+        transactions: &[Vec<u8>],
+        chain: &str,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        if !self.supports_chain(chain) {
+            return Err(WalletError::UnsupportedChain(chain.to_string()));
+        }
+
+        if !wallet.solana_sign_transaction() {
+            return Err(WalletError::MissingSignTransactionFunction);
+        }
+
+        wallet
+            .sign_all_transactions(transactions, Some(chain.try_into()?), self)
+            .await
+    }
+
+    /// Request the browser `wallet` to sign a serialized transaction using this account
+    /// and send it to the `chain`, returning the [Signature] of the transaction.
+    /// If `options` is `None` the default [SendOptions] are used.
+    /// Returns [WalletError::UnsupportedChain] if the account does not support the `chain`
+    pub async fn sign_and_send_transaction(
+        &self,
+        wallet: &Wallet,
+        transaction_bytes: &[u8],
+        chain: &str,
+        options: Option<SendOptions>,
+    ) -> WalletResult<Signature> {
+        if !self.supports_chain(chain) {
+            return Err(WalletError::UnsupportedChain(chain.to_string()));
+        }
+
+        if !wallet.solana_sign_and_send_transaction() {
+            return Err(WalletError::MissingSignAndSendTransactionFunction);
+        }
+
+        wallet
+            .sign_and_send_transaction(
+                transaction_bytes,
+                chain.try_into()?,
+                options.unwrap_or_default(),
+                self,
+            )
+            .await
+    }
+
+    /// Request the browser `wallet` to sign and send a serialized transaction like
+    /// [Self::sign_and_send_transaction], retrying up to `retries` times when the request
+    /// fails with a transient error as determined by [WalletError::is_transient].
+    /// The delay before each retry starts at [RETRY_BASE_DELAY_MS] and doubles
+    /// after every retry. Other errors, like [WalletError::UserRejected]
+    /// or a missing feature, are returned without retrying. This includes an expired
+    /// blockhash, in which case the transaction has to be rebuilt with a new blockhash
+    pub async fn sign_and_send_with_retry(
+        &self,
+        wallet: &Wallet,
+        transaction_bytes: &[u8],
+        chain: &str,
+        options: Option<SendOptions>,
+        retries: u8,
+    ) -> WalletResult<Signature> {
+        let mut attempt = 0u8;
+
+        loop {
+            match self
+                .sign_and_send_transaction(wallet, transaction_bytes, chain, options)
+                .await
+            {
+                Err(error) if error.is_transient() && attempt < retries => {
+                    let delay = RETRY_BASE_DELAY_MS.saturating_mul(1u32 << attempt.min(16));
+                    Utils::sleep(delay).await?;
+
+                    attempt += 1;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
     /// Parse A [WalletAccount] from [JsValue]
     pub(crate) fn parse(reflection: Reflection) -> WalletResult<Self> {
         let address = reflection.string("address")?;
@@ -130,27 +318,27 @@ impl WalletAccount {
 
         let mut supported_features = FeatureSupport::default();
 
-        features.iter().try_for_each(|feature| {
-            if feature.as_str() == STANDARD_CONNECT_IDENTIFIER {
+        // Features other than the standard features, like `vendor:stake`, are only kept
+        // in `features`, see `Self::supports_feature`
+        features.iter().for_each(|feature| {
+            let feature = Utils::canonical_feature_name(feature).unwrap_or(feature);
+
+            if feature == STANDARD_CONNECT_IDENTIFIER {
                 supported_features.connect = true;
-            } else if feature.as_str() == STANDARD_DISCONNECT_IDENTIFIER {
+            } else if feature == STANDARD_DISCONNECT_IDENTIFIER {
                 supported_features.disconnect = true;
-            } else if feature.as_str() == STANDARD_EVENTS_IDENTIFIER {
+            } else if feature == STANDARD_EVENTS_IDENTIFIER {
                 supported_features.events = true;
-            } else if feature.as_str() == SOLANA_SIGN_IN_IDENTIFIER {
+            } else if feature == SOLANA_SIGN_IN_IDENTIFIER {
                 supported_features.sign_in = true;
-            } else if feature.as_str() == SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER {
+            } else if feature == SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER {
                 supported_features.sign_and_send_tx = true;
-            } else if feature.as_str() == SOLANA_SIGN_TRANSACTION_IDENTIFIER {
+            } else if feature == SOLANA_SIGN_TRANSACTION_IDENTIFIER {
                 supported_features.sign_tx = true;
-            } else if feature.as_str() == SOLANA_SIGN_MESSAGE_IDENTIFIER {
+            } else if feature == SOLANA_SIGN_MESSAGE_IDENTIFIER {
                 supported_features.sign_message = true;
-            } else {
-                return Err(WalletError::UnsupportedWalletFeature(feature.to_owned()));
             }
-
-            Ok(())
-        })?;
+        });
 
         let icon = WalletIcon::from_jsvalue(&reflection)?;
 
@@ -174,9 +362,22 @@ impl WalletAccount {
             supported_chains,
             supported_features,
             js_value: reflection.take(),
+            connected_at: Option::None,
         })
     }
 
+    /// Checks if the `chain` is supported by the account. The `chain` is parsed
+    /// the same way as a [Cluster], for example `solana:devnet` or `devnet`
+    pub fn supports_chain(&self, chain: &str) -> bool {
+        match Cluster::try_from(chain) {
+            Ok(Cluster::MainNet) => self.mainnet(),
+            Ok(Cluster::DevNet) => self.devnet(),
+            Ok(Cluster::TestNet) => self.testnet(),
+            Ok(Cluster::LocalNet) => self.localnet(),
+            Err(_) => false,
+        }
+    }
+
     /// Checks if MainNet is supported
     pub fn mainnet(&self) -> bool {
         self.supported_chains.mainnet
@@ -231,17 +432,51 @@ impl WalletAccount {
     pub fn solana_sign_transaction(&self) -> bool {
         self.supported_features.sign_tx
     }
+
+    /// Check whether this account supports the `feature`, which can differ from the features
+    /// of its wallet, for example a watch-only account of a hardware wallet.
+    /// Standard feature names are matched after normalization using [Utils::canonical_feature_name]
+    pub fn supports_feature(&self, feature: &str) -> bool {
+        match Utils::canonical_feature_name(feature) {
+            Some(STANDARD_CONNECT_IDENTIFIER) => self.standard_connect(),
+            Some(STANDARD_DISCONNECT_IDENTIFIER) => self.standard_disconnect(),
+            Some(STANDARD_EVENTS_IDENTIFIER) => self.standard_events(),
+            Some(SOLANA_SIGN_IN_IDENTIFIER) => self.solana_signin(),
+            Some(SOLANA_SIGN_MESSAGE_IDENTIFIER) => self.solana_sign_message(),
+            Some(SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER) => {
+                self.solana_sign_and_send_transaction()
+            }
+            Some(SOLANA_SIGN_TRANSACTION_IDENTIFIER) => self.solana_sign_transaction(),
+            _ => self.features.iter().any(|supported| supported == feature),
+        }
+    }
+
+    /// Check whether this account is watch-only, meaning it supports none of
+    /// `solana:signMessage`, `solana:signTransaction` and `solana:signAndSendTransaction`
+    /// so it can be displayed but cannot sign
+    pub fn is_watch_only(&self) -> bool {
+        !self.solana_sign_message()
+            && !self.solana_sign_transaction()
+            && !self.solana_sign_and_send_transaction()
+    }
 }
 
+/// The address is shortened to the first 4 and last 4 characters and the public key
+/// is omitted so logs do not contain the full public key.
+/// Use [WalletAccount::address] or [WalletAccount::public_key] for the full public key
 impl core::fmt::Debug for WalletAccount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let address = self
+            .shorten_address()
+            .unwrap_or(Cow::Borrowed(self.address.as_str()));
+
         f.debug_struct("WalletAccount")
-            .field("address", &self.address)
-            .field("public_key", &self.public_key)
+            .field("address", &address)
             .field("chains", &self.chains)
             .field("features", &self.features)
             .field("label", &self.label)
             .field("icon", &self.icon)
+            .field("connected_at", &self.connected_at)
             .finish()
     }
 }
@@ -294,3 +529,111 @@ impl<'a> From<&'a WalletAccount> for InnerWalletAccount<'a> {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_account_tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn connected_duration_ms() {
+        let mut account = WalletAccount::default();
+
+        assert!(account.connected_duration_ms().is_none());
+
+        account.set_connected_now();
+
+        assert!(account.connected_duration_ms().unwrap() >= 0f64);
+    }
+
+    fn account_object(features: &[&str]) -> Reflection {
+        use web_sys::js_sys::{Array, Uint8Array};
+
+        let mut account = Reflection::new_object();
+        account.set_object_str("address", "Foo").unwrap();
+        account
+            .set_object(&"publicKey".into(), &Uint8Array::from([1u8; 32].as_slice()))
+            .unwrap();
+        account
+            .set_object(&"chains".into(), &Array::of1(&"solana:devnet".into()))
+            .unwrap();
+        account
+            .set_object(
+                &"features".into(),
+                &features
+                    .iter()
+                    .map(|feature| JsValue::from(*feature))
+                    .collect::<Array>(),
+            )
+            .unwrap();
+
+        account
+    }
+
+    #[wasm_bindgen_test]
+    fn supports_feature() {
+        let account = WalletAccount::parse(account_object(&[
+            SOLANA_SIGN_MESSAGE_IDENTIFIER,
+            "vendor:stake",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            account.features(),
+            [SOLANA_SIGN_MESSAGE_IDENTIFIER, "vendor:stake"]
+        );
+        assert!(account.supports_feature(SOLANA_SIGN_MESSAGE_IDENTIFIER));
+        assert!(account.supports_feature("solana:sign-message"));
+        assert!(!account.supports_feature(SOLANA_SIGN_TRANSACTION_IDENTIFIER));
+        assert!(account.supports_feature("vendor:stake"));
+        assert!(!account.supports_feature("vendor:unstake"));
+        assert!(!account.is_watch_only());
+
+        let watch_only = WalletAccount::parse(account_object(&["vendor:stake"])).unwrap();
+        assert!(watch_only.supports_feature("vendor:stake"));
+        assert!(watch_only.is_watch_only());
+    }
+
+    #[wasm_bindgen_test]
+    fn debug_is_redacted() {
+        let account = WalletAccount {
+            address: "FEJi6GTjFjGSU8nFeskNdTjLYXpSVi1V8eQ3nLLnVeFm".to_string(),
+            public_key: [7u8; 32],
+            ..Default::default()
+        };
+
+        let debug = format!("{account:?}");
+        assert!(debug.contains("FEJi...VeFm"));
+        assert!(!debug.contains(account.address()));
+        assert!(!debug.contains("7, 7"));
+    }
+
+    #[wasm_bindgen_test]
+    fn label() {
+        let mut account = account_object(&[]);
+
+        assert!(WalletAccount::parse(account.clone())
+            .unwrap()
+            .label()
+            .is_none());
+
+        account.set_object_str("label", "Trading Wallet").unwrap();
+        assert_eq!(
+            WalletAccount::parse(account).unwrap().label(),
+            Some("Trading Wallet")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn supports_chain() {
+        let mut account = WalletAccount::default();
+        assert!(!account.supports_chain("solana:devnet"));
+
+        account.supported_chains.devnet = true;
+        assert!(account.supports_chain("solana:devnet"));
+        assert!(account.supports_chain("devnet"));
+        assert!(!account.supports_chain("solana:mainnet"));
+        assert!(!account.supports_chain("ethereum:1"));
+    }
+}